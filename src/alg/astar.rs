@@ -1,17 +1,11 @@
 //! A* (A-Star) 算法模块
 
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::cmp::Ordering;
-use crate::map::Map;
-use crate::point::{Point, Direction, Color};
-
-/// 方向偏移量: (dx, dy, direction)
-const DIRECTIONS: [(i32, i32, Direction); 4] = [
-    (0, 1, Direction::Down),
-    (1, 0, Direction::Right),
-    (0, -1, Direction::Up),
-    (-1, 0, Direction::Left),
-];
+use std::time::Instant;
+use crate::map::{CellState, Map};
+use crate::point::{Point, Color};
+use super::{SearchLimits, SearchStats, SearchStatus};
 
 /// 用于优先队列的节点包装
 #[derive(Debug, Clone)]
@@ -43,11 +37,14 @@ impl PartialOrd for AStarNode {
 
 /// A* 搜索结果
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AStarResult {
     pub found: bool,
     pub steps: Vec<Point>,
     pub path_length: i32,
     pub path: Vec<Point>,
+    pub stats: SearchStats,
+    pub status: SearchStatus,
 }
 
 /// A* 搜索器
@@ -57,24 +54,45 @@ pub struct AStar {
     sy: i32,
     ex: i32,
     ey: i32,
+    portal_cost: i32,    // 传送门最小代价
+    portal_exit: i32,    // 任一传送门端点到终点的最小估计距离
+    limits: SearchLimits,
 }
 
 impl AStar {
     /// 创建新的 A* 搜索器
     pub fn new(map: Map, sx: i32, sy: i32, ex: i32, ey: i32) -> Self {
-        Self { map, sx, sy, ex, ey }
+        let portal_cost = map.portals.iter().map(|p| p.cost).min().unwrap_or(0);
+        let portal_exit = map.portals.iter()
+            .flat_map(|p| [p.a, p.b])
+            .map(|(x, y)| map.estimate(x, y, ex, ey))
+            .min()
+            .unwrap_or(0);
+        Self { map, sx, sy, ex, ey, portal_cost, portal_exit, limits: SearchLimits::default() }
+    }
+    
+    /// 设置搜索限制（扩展节点预算、截止时间、取消标志）
+    pub fn with_limits(mut self, limits: SearchLimits) -> Self {
+        self.limits = limits;
+        self
     }
     
-    /// 计算曼哈顿距离（启发式函数）
+    /// 启发式函数：曼哈顿距离与"经任一传送门"下界中的较小者，保证可采纳
     fn heuristic(&self, x: i32, y: i32) -> i32 {
-        (self.ex - x).abs() + (self.ey - y).abs()
+        let direct = self.map.estimate(x, y, self.ex, self.ey);
+        self.map.portals.iter()
+            .flat_map(|p| [p.a, p.b])
+            .map(|(px, py)| self.map.estimate(x, y, px, py) + self.portal_cost + self.portal_exit)
+            .fold(direct, i32::min)
     }
     
     /// 执行搜索（返回所有步骤，用于可视化）
     pub fn search(&mut self) -> AStarResult {
+        let begin = Instant::now();
         let mut steps = Vec::new();
         let mut open_set = BinaryHeap::new();
         let mut pre: HashMap<(i32, i32), Point> = HashMap::new();
+        let mut g_score: HashMap<(i32, i32), i32> = HashMap::new();
         
         let mut start = Point::with_color(self.sx, self.sy, Color::YELLOW);
         start.step = 0;
@@ -86,13 +104,27 @@ impl AStar {
             point: start.clone(),
             f_cost: start.f_cost(),
         });
-        self.map.set_cell(self.sx, self.sy, 4);
+        g_score.insert((self.sx, self.sy), 0);
+        self.map.set_cell(self.sx, self.sy, CellState::Start);
         steps.push(start.clone());
         
-        let mut found = false;
+        let mut status = SearchStatus::Unreachable;
         let mut final_step = 0;
+        let mut stats = SearchStats { nodes_generated: 1, max_frontier: 1, ..Default::default() };
+        let mut closed = HashSet::new();
         
         while let Some(AStarNode { point: current, .. }) = open_set.pop() {
+            // 跳过已被更优代价取代的过期节点
+            if g_score.get(&(current.x, current.y)).is_some_and(|&g| g < current.step) {
+                continue;
+            }
+            
+            if let Some(stopped) = self.limits.check(stats.nodes_expanded) {
+                status = stopped;
+                break;
+            }
+            stats.nodes_expanded += 1;
+            closed.insert((current.x, current.y));
             steps.push(current.clone());
             
             if current == end {
@@ -103,7 +135,7 @@ impl AStar {
                 // 回溯路径
                 let mut path_point = end.clone();
                 while path_point != start {
-                    self.map.set_cell(path_point.x, path_point.y, 3);
+                    self.map.set_cell(path_point.x, path_point.y, CellState::Backtrack);
                     if let Some(prev) = pre.get(&(path_point.x, path_point.y)) {
                         let mut prev_display = prev.clone();
                         prev_display.color = Color::LIGHT_RED;
@@ -118,43 +150,59 @@ impl AStar {
                 final_start.color = Color::YELLOW;
                 steps.push(final_start);
                 
-                found = true;
+                status = SearchStatus::Found;
                 final_step = current.step;
                 break;
             }
             
-            for (dx, dy, dir) in DIRECTIONS.iter() {
-                let nx = current.x + dx;
-                let ny = current.y + dy;
+            for edge in self.map.neighbors(current.x, current.y) {
+                let (nx, ny) = (edge.x, edge.y);
+                let tentative = current.step + edge.cost;
                 
-                if !self.map.in_bounds(nx, ny) || self.map.get_cell(nx, ny) != 0 {
+                // 未访问的格子直接加入；已加入的格子仅在找到更短路径时更新
+                let better = match self.map.get_cell(nx, ny) {
+                    CellState::Path => true,
+                    CellState::Visited => g_score.get(&(nx, ny)).is_some_and(|&g| tentative < g),
+                    _ => false,
+                };
+                if !better {
                     continue;
                 }
                 
                 let mut neighbor = Point::new(nx, ny);
-                neighbor.step = current.step + 1;
+                neighbor.step = tentative;
                 neighbor.h_cost = self.heuristic(nx, ny);
-                neighbor.direction = *dir;
+                neighbor.direction = edge.direction;
                 neighbor.color = Color::SKY_BLUE;
                 
                 open_set.push(AStarNode {
                     f_cost: neighbor.f_cost(),
                     point: neighbor,
                 });
+                stats.nodes_generated += 1;
+                if closed.remove(&(nx, ny)) {
+                    stats.reopened += 1;
+                }
                 
+                g_score.insert((nx, ny), tentative);
                 pre.insert((nx, ny), current.clone());
-                self.map.set_cell(nx, ny, 2);
+                self.map.set_cell(nx, ny, CellState::Visited);
             }
+            stats.max_frontier = stats.max_frontier.max(open_set.len());
         }
         
         // 构建最终路径
-        let path = self.reconstruct_path(&pre, &start, &end);
+        let found = status == SearchStatus::Found;
+        let path = if found { self.reconstruct_path(&pre, &start, &end) } else { Vec::new() };
+        stats.elapsed = begin.elapsed();
         
         AStarResult {
             found,
             steps,
             path_length: final_step,
             path,
+            stats,
+            status,
         }
     }
     
@@ -185,6 +233,11 @@ impl AStar {
     pub fn map_mut(&mut self) -> &mut Map {
         &mut self.map
     }
+    
+    /// 取出搜索后的地图
+    pub fn into_map(self) -> Map {
+        self.map
+    }
 }
 
 /// 执行 A* 搜索的便捷函数
@@ -207,4 +260,22 @@ mod tests {
         let result = astar_search(&mut map, sx, sy, ex, ey);
         println!("A* found: {}, path length: {}", result.found, result.path_length);
     }
+    
+    #[test]
+    fn test_astar_portal_shortcut() {
+        // 不经传送门：向右 6 步、向下 2 步、向左 6 步，共 14；经 (2, 1) ⇄ (2, 3) 传送门：1 + 代价 + 1
+        let text = "#########\n#S......#\n#######.#\n#G......#\n#########\n";
+        for (portal_cost, optimal) in [(3, 5), (20, 14)] {
+            let mut map = Map::from_text(text, 90, 50).unwrap();
+            map.add_portal((2, 1), (2, 3), portal_cost);
+            let result = astar_search(&mut map.clone(), 1, 1, 1, 3);
+            assert!(result.found);
+            assert_eq!(result.path_length, optimal, "portal cost {}", portal_cost);
+        }
+
+        // BFS 只保证步数最少：昂贵的传送门仍只需 3 步
+        let mut map = Map::from_text(text, 90, 50).unwrap();
+        map.add_portal((2, 1), (2, 3), 20);
+        assert_eq!(crate::bfs_search(&mut map, 1, 1, 1, 3).path_length, 22);
+    }
 }
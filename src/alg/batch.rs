@@ -0,0 +1,75 @@
+//! 批量求解模块 - 使用 rayon 并行求解大量互相独立的任务
+//!
+//! 每个任务拥有自己的地图，任务之间不共享状态；结果按输入顺序返回，与逐个调用 `solve_limited` 相同。
+
+use rayon::prelude::*;
+use crate::map::Map;
+use super::{Algorithm, SearchLimits, SearchResult, solve_limited};
+
+/// 批量求解中的一个任务
+#[derive(Debug, Clone)]
+pub struct SolveJob {
+    pub map: Map,
+    pub start: (i32, i32),
+    pub goal: (i32, i32),
+    pub algorithm: Algorithm,
+    pub limits: SearchLimits,
+}
+
+impl SolveJob {
+    /// 在地图默认的起点、终点之间求解
+    pub fn new(map: Map, algorithm: Algorithm) -> Self {
+        let (start, goal) = (map.start(), map.end());
+        Self { map, start, goal, algorithm, limits: SearchLimits::default() }
+    }
+
+    /// 设置搜索限制
+    pub fn with_limits(mut self, limits: SearchLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+}
+
+/// 并行求解全部任务，按输入顺序返回结果
+pub fn solve_batch(jobs: Vec<SolveJob>) -> Vec<SearchResult> {
+    solve_batch_with(jobs, |_, result| result)
+}
+
+/// 并行求解全部任务，并在各工作线程中用 f(任务序号, 结果) 转换结果，按输入顺序返回
+///
+/// 大批量任务只需要统计量时，可在 f 中丢弃步骤轨迹，避免同时保留所有结果的内存开销。
+pub fn solve_batch_with<T, F>(jobs: Vec<SolveJob>, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize, SearchResult) -> T + Sync,
+{
+    jobs.into_par_iter()
+        .enumerate()
+        .map(|(i, mut job)| f(i, solve_limited(&mut job.map, job.algorithm, job.start, job.goal, &job.limits)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::Topology;
+
+    #[test]
+    fn test_solve_batch_preserves_order() {
+        let jobs: Vec<SolveJob> = (0..40)
+            .map(|seed| {
+                let map = Map::seeded(Topology::Square, 15 + seed as i32 % 7 * 2, 15, seed, 0, 0).unwrap();
+                SolveJob::new(map, Algorithm::ALL[seed as usize % Algorithm::ALL.len()])
+            })
+            .collect();
+        let expected: Vec<i32> = jobs.iter()
+            .map(|job| crate::solve(&mut job.map.clone(), job.algorithm).path_length)
+            .collect();
+
+        let results = solve_batch(jobs.clone());
+        assert_eq!(results.iter().map(|r| r.path_length).collect::<Vec<_>>(), expected);
+
+        let indexed = solve_batch_with(jobs, |i, r| (i, r.found, r.stats.nodes_expanded));
+        assert!(indexed.iter().enumerate().all(|(i, &(j, found, expanded))| i == j && found && expanded > 0));
+    }
+}
@@ -1,43 +1,51 @@
 //! BFS (广度优先搜索) 算法模块
 
 use std::collections::{VecDeque, HashMap};
-use crate::map::Map;
-use crate::point::{Point, Direction, Color};
-
-/// 方向偏移量: (dx, dy, direction)
-const DIRECTIONS: [(i32, i32, Direction); 4] = [
-    (0, 1, Direction::Down),
-    (1, 0, Direction::Right),
-    (0, -1, Direction::Up),
-    (-1, 0, Direction::Left),
-];
+use std::time::Instant;
+use crate::map::{CellState, Map};
+use crate::point::{Point, Color};
+use super::{SearchLimits, SearchStats, SearchStatus};
 
 /// BFS 搜索结果
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BfsResult {
     pub found: bool,
     pub steps: Vec<Point>,
     pub path_length: i32,
     pub path: Vec<Point>,
+    pub stats: SearchStats,
+    pub status: SearchStatus,
 }
 
 /// BFS 搜索器
+///
+/// 按先进先出顺序扩展，结果只保证步数（边数）最少；`path_length` 为该路径的代价之和，
+/// 有传送门或地形权重时不一定是最小代价，需要最小代价时使用 A*。
 pub struct BFS {
     map: Map,
     sx: i32,
     sy: i32,
     ex: i32,
     ey: i32,
+    limits: SearchLimits,
 }
 
 impl BFS {
     /// 创建新的 BFS 搜索器
     pub fn new(map: Map, sx: i32, sy: i32, ex: i32, ey: i32) -> Self {
-        Self { map, sx, sy, ex, ey }
+        Self { map, sx, sy, ex, ey, limits: SearchLimits::default() }
+    }
+    
+    /// 设置搜索限制（扩展节点预算、截止时间、取消标志）
+    pub fn with_limits(mut self, limits: SearchLimits) -> Self {
+        self.limits = limits;
+        self
     }
     
     /// 执行搜索（返回所有步骤，用于可视化）
     pub fn search(&mut self) -> BfsResult {
+        let begin = Instant::now();
         let mut steps = Vec::new();
         let mut queue = VecDeque::new();
         let mut pre: HashMap<(i32, i32), Point> = HashMap::new();
@@ -46,13 +54,19 @@ impl BFS {
         let end = Point::new(self.ex, self.ey);
         
         queue.push_back(start.clone());
-        self.map.set_cell(self.sx, self.sy, 4);
+        self.map.set_cell(self.sx, self.sy, CellState::Start);
         steps.push(start.clone());
         
-        let mut found = false;
+        let mut status = SearchStatus::Unreachable;
         let mut final_step = 0;
+        let mut stats = SearchStats { nodes_generated: 1, max_frontier: 1, ..Default::default() };
         
         while let Some(current) = queue.pop_front() {
+            if let Some(stopped) = self.limits.check(stats.nodes_expanded) {
+                status = stopped;
+                break;
+            }
+            stats.nodes_expanded += 1;
             steps.push(current.clone());
             
             if current == end {
@@ -63,7 +77,7 @@ impl BFS {
                 // 回溯路径
                 let mut path_point = end_point;
                 while path_point != start {
-                    self.map.set_cell(path_point.x, path_point.y, 3);
+                    self.map.set_cell(path_point.x, path_point.y, CellState::Backtrack);
                     if let Some(prev) = pre.get(&(path_point.x, path_point.y)) {
                         let mut prev_display = prev.clone();
                         prev_display.color = Color::LIGHT_RED;
@@ -79,44 +93,49 @@ impl BFS {
                 final_start.color = Color::YELLOW;
                 steps.push(final_start);
                 
-                found = true;
+                status = SearchStatus::Found;
                 final_step = current.step;
                 break;
             }
             
-            for (dx, dy, dir) in DIRECTIONS.iter() {
-                let nx = current.x + dx;
-                let ny = current.y + dy;
+            for edge in self.map.neighbors(current.x, current.y) {
+                let (nx, ny) = (edge.x, edge.y);
                 
-                if self.map.in_bounds(nx, ny) && self.map.get_cell(nx, ny) == 0 {
+                if self.map.get_cell(nx, ny) == CellState::Path {
                     let next = Point::full(
                         nx, ny,
-                        current.step + 1,
+                        current.step + edge.cost,
                         0,
-                        *dir,
+                        edge.direction,
                         Color::SKY_BLUE
                     );
                     queue.push_back(next);
+                    stats.nodes_generated += 1;
                     pre.insert((nx, ny), Point::full(
                         current.x, current.y,
                         current.step,
                         0,
-                        *dir,
+                        edge.direction,
                         current.color
                     ));
-                    self.map.set_cell(nx, ny, 2);
+                    self.map.set_cell(nx, ny, CellState::Visited);
                 }
             }
+            stats.max_frontier = stats.max_frontier.max(queue.len());
         }
         
         // 构建最终路径
-        let path = self.reconstruct_path(&pre, &start, &end);
+        let found = status == SearchStatus::Found;
+        let path = if found { self.reconstruct_path(&pre, &start, &end) } else { Vec::new() };
+        stats.elapsed = begin.elapsed();
         
         BfsResult {
             found,
             steps,
             path_length: final_step,
             path,
+            stats,
+            status,
         }
     }
     
@@ -147,6 +166,11 @@ impl BFS {
     pub fn map_mut(&mut self) -> &mut Map {
         &mut self.map
     }
+    
+    /// 取出搜索后的地图
+    pub fn into_map(self) -> Map {
+        self.map
+    }
 }
 
 /// 执行 BFS 搜索的便捷函数
@@ -169,4 +193,15 @@ mod tests {
         let result = bfs_search(&mut map, sx, sy, ex, ey);
         println!("BFS found: {}, path length: {}", result.found, result.path_length);
     }
+    
+    #[test]
+    fn test_bfs_multi_floor() {
+        let mut map = Map::with_floors(10, 10, 3, 100, 100);
+        let (sx, sy) = map.start();
+        let (ex, ey) = map.end();
+        let result = bfs_search(&mut map, sx, sy, ex, ey);
+        assert!(result.found);
+        assert_eq!(result.path.last().map(|p| (p.x, p.y)), Some((ex, ey)));
+        assert!(result.path_length >= map.estimate(sx, sy, ex, ey));
+    }
 }
@@ -1,43 +1,51 @@
 //! DBFS (双向广度优先搜索) 算法模块
 
 use std::collections::{VecDeque, HashMap};
-use crate::map::Map;
+use std::time::Instant;
+use crate::map::{CellState, Map, Edge};
 use crate::point::{Point, Direction, Color};
-
-/// 方向偏移量: (dx, dy, direction)
-const DIRECTIONS: [(i32, i32, Direction); 4] = [
-    (0, 1, Direction::Down),
-    (1, 0, Direction::Right),
-    (0, -1, Direction::Up),
-    (-1, 0, Direction::Left),
-];
+use super::{SearchLimits, SearchStats, SearchStatus};
 
 /// DBFS 搜索结果
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DbfsResult {
     pub found: bool,
     pub steps: Vec<Point>,
     pub path_length: i32,
     pub path: Vec<Point>,
+    pub stats: SearchStats,
+    pub status: SearchStatus,
 }
 
 /// DBFS 搜索器
+///
+/// 两侧轮流扩展一整层，在该层的所有相遇点中取步数最少的一个，因此与 BFS 相同，结果保证步数（边数）最少；
+/// 有传送门或地形权重时 `path_length` 不一定是最小代价。
 pub struct DBFS {
     map: Map,
     sx: i32,
     sy: i32,
     ex: i32,
     ey: i32,
+    limits: SearchLimits,
 }
 
 impl DBFS {
     /// 创建新的 DBFS 搜索器
     pub fn new(map: Map, sx: i32, sy: i32, ex: i32, ey: i32) -> Self {
-        Self { map, sx, sy, ex, ey }
+        Self { map, sx, sy, ex, ey, limits: SearchLimits::default() }
+    }
+    
+    /// 设置搜索限制（扩展节点预算、截止时间、取消标志）
+    pub fn with_limits(mut self, limits: SearchLimits) -> Self {
+        self.limits = limits;
+        self
     }
     
     /// 执行搜索（返回所有步骤，用于可视化）
     pub fn search(&mut self) -> DbfsResult {
+        let begin = Instant::now();
         let mut steps = Vec::new();
         let mut start_queue = VecDeque::new();
         let mut end_queue = VecDeque::new();
@@ -49,96 +57,125 @@ impl DBFS {
         
         start_queue.push_back(start.clone());
         end_queue.push_back(end.clone());
-        self.map.set_cell(self.sx, self.sy, 4);
-        self.map.set_cell(self.ex, self.ey, 4);
+        self.map.set_cell(self.sx, self.sy, CellState::Start);
+        self.map.set_cell(self.ex, self.ey, CellState::Start);
         steps.push(start.clone());
         
-        let mut found = false;
-        let mut meeting_point: Option<(Point, Point)> = None;
+        let mut status = SearchStatus::Unreachable;
+        // 相遇时的 (路径步数, 起点侧相遇点, 终点侧相遇点)
+        let mut meeting_point: Option<(usize, Point, Point)> = None;
+        // 两侧已到达格子的步数，用于比较不同相遇点的路径步数
+        let mut start_depth: HashMap<(i32, i32), usize> = HashMap::from([((self.sx, self.sy), 0)]);
+        let mut end_depth: HashMap<(i32, i32), usize> = HashMap::from([((self.ex, self.ey), 0)]);
+        let mut stats = SearchStats { nodes_generated: 2, max_frontier: 2, ..Default::default() };
         
-        while !start_queue.is_empty() && !end_queue.is_empty() {
-            // 选择较小的队列进行扩展
+        'search: while !start_queue.is_empty() && !end_queue.is_empty() {
+            // 选择较小的队列，扩展其当前的一整层
             let expand_start = start_queue.len() <= end_queue.len();
+            let level = if expand_start { start_queue.len() } else { end_queue.len() };
             
-            let (x, y, step, color) = if expand_start {
-                let current = start_queue.pop_front().unwrap();
-                (current.x, current.y, current.step, current.color)
-            } else {
-                let current = end_queue.pop_front().unwrap();
-                (current.x, current.y, current.step, current.color)
-            };
-            
-            steps.push(Point::full(x, y, step, 0, Direction::None, color));
-            
-            for (dx, dy, dir) in DIRECTIONS.iter() {
-                let nx = x + dx;
-                let ny = y + dy;
-                
-                if !self.map.in_bounds(nx, ny) {
-                    continue;
+            for _ in 0..level {
+                if let Some(stopped) = self.limits.check(stats.nodes_expanded) {
+                    status = stopped;
+                    break 'search;
                 }
                 
-                let cell = self.map.get_cell(nx, ny);
+                let current = if expand_start { start_queue.pop_front() } else { end_queue.pop_front() }.unwrap();
+                let (x, y, step, color) = (current.x, current.y, current.step, current.color);
                 
-                if cell == 1 {
-                    continue; // 墙
-                }
+                stats.nodes_expanded += 1;
+                steps.push(Point::full(x, y, step, 0, Direction::None, color));
+                
+                // 反向搜索沿边的逆方向扩展（单向门）
+                let edges = if expand_start {
+                    self.map.neighbors(x, y)
+                } else {
+                    self.map.predecessors(x, y)
+                        .into_iter()
+                        .map(|e| Edge { direction: e.direction.reverse(), ..e })
+                        .collect()
+                };
+                let (depth, other_depth) = if expand_start { (&mut start_depth, &end_depth) } else { (&mut end_depth, &start_depth) };
+                let next = depth[&(x, y)] + 1;
                 
-                if cell == 0 {
-                    // 未访问的格子
-                    if expand_start {
-                        self.map.set_cell(nx, ny, 2);
-                        start_queue.push_back(Point::full(
-                            nx, ny,
-                            step + 1,
-                            0,
-                            *dir,
-                            Color::SKY_BLUE
-                        ));
-                        start_pre.insert((nx, ny), Point::with_direction(x, y, *dir));
-                    } else {
-                        self.map.set_cell(nx, ny, 3);
-                        end_queue.push_back(Point::full(
-                            nx, ny,
-                            step + 1,
-                            0,
-                            *dir,
-                            Color::LIGHT_RED
-                        ));
-                        end_pre.insert((nx, ny), Point::with_direction(x, y, *dir));
+                for edge in edges {
+                    let (nx, ny, dir) = (edge.x, edge.y, &edge.direction);
+                    
+                    let cell = self.map.get_cell(nx, ny);
+                    
+                    if cell == CellState::Wall {
+                        continue; // 墙
+                    }
+                    
+                    if cell == CellState::Path {
+                        // 未访问的格子
+                        stats.nodes_generated += 1;
+                        depth.insert((nx, ny), next);
+                        if expand_start {
+                            self.map.set_cell(nx, ny, CellState::Visited);
+                            start_queue.push_back(Point::full(
+                                nx, ny,
+                                step + edge.cost,
+                                0,
+                                *dir,
+                                Color::SKY_BLUE
+                            ));
+                            start_pre.insert((nx, ny), Point::with_direction(x, y, *dir));
+                        } else {
+                            self.map.set_cell(nx, ny, CellState::Backtrack);
+                            end_queue.push_back(Point::full(
+                                nx, ny,
+                                step + edge.cost,
+                                0,
+                                *dir,
+                                Color::LIGHT_RED
+                            ));
+                            end_pre.insert((nx, ny), Point::with_direction(x, y, *dir));
+                        }
+                    } else if let Some(&rest) = other_depth.get(&(nx, ny)) {
+                        // 两个搜索相遇（或直接到达另一侧尚未扩展的端点），保留本层中步数最少的相遇点
+                        let hops = next + rest;
+                        if meeting_point.as_ref().is_none_or(|(best, _, _)| hops < *best) {
+                            let (start_point, end_point) = if expand_start {
+                                (Point::with_direction(x, y, *dir), Point::with_direction(nx, ny, dir.reverse()))
+                            } else {
+                                (Point::with_direction(nx, ny, dir.reverse()), Point::with_direction(x, y, *dir))
+                            };
+                            meeting_point = Some((hops, start_point, end_point));
+                        }
                     }
-                } else if (cell == 2 && !expand_start) || (cell == 3 && expand_start) {
-                    // 两个搜索相遇
-                    let (start_point, end_point) = if expand_start {
-                        (Point::with_direction(x, y, *dir), Point::with_direction(nx, ny, dir.reverse()))
-                    } else {
-                        (Point::with_direction(nx, ny, dir.reverse()), Point::with_direction(x, y, *dir))
-                    };
-                    meeting_point = Some((start_point, end_point));
-                    found = true;
-                    break;
                 }
             }
+            stats.max_frontier = stats.max_frontier.max(start_queue.len() + end_queue.len());
             
-            if found {
+            // 一整层扩展完后，本层的最优相遇点即为步数最少的路径
+            if meeting_point.is_some() {
+                status = SearchStatus::Found;
                 break;
             }
         }
         
         // 构建最终路径
-        let path = if let Some((start_meet, end_meet)) = meeting_point {
-            self.reconstruct_path(&start_pre, &end_pre, &start, &end, &start_meet, &end_meet, &mut steps)
+        let found = status == SearchStatus::Found;
+        let path = if let (true, Some((_, start_meet, end_meet))) = (found, meeting_point) {
+            self.reconstruct_path(&start_pre, &end_pre, (&start, &end), (start_meet, end_meet), &mut steps)
         } else {
             Vec::new()
         };
         
-        let path_length = path.len() as i32 - 1;
+        // 路径代价（传送门等边的代价可能不为 1）
+        let path_length = path.windows(2)
+            .map(|w| self.map.edge_cost(w[0].x, w[0].y, w[1].x, w[1].y).unwrap_or(1))
+            .sum();
+        stats.elapsed = begin.elapsed();
         
         DbfsResult {
             found,
             steps,
-            path_length: if path_length < 0 { 0 } else { path_length },
+            path_length,
             path,
+            stats,
+            status,
         }
     }
     
@@ -147,17 +184,15 @@ impl DBFS {
         &mut self,
         start_pre: &HashMap<(i32, i32), Point>,
         end_pre: &HashMap<(i32, i32), Point>,
-        start: &Point,
-        end: &Point,
-        start_meet: &Point,
-        end_meet: &Point,
+        (start, end): (&Point, &Point),
+        (start_meet, end_meet): (Point, Point),
         steps: &mut Vec<Point>
     ) -> Vec<Point> {
         let mut path = Vec::new();
         
         // 从起点到相遇点的路径
         let mut start_path = Vec::new();
-        let mut current = start_meet.clone();
+        let mut current = start_meet;
         while current != *start {
             start_path.push(current.clone());
             if let Some(prev) = start_pre.get(&(current.x, current.y)) {
@@ -171,7 +206,7 @@ impl DBFS {
         
         // 从相遇点到终点的路径
         let mut end_path = Vec::new();
-        current = end_meet.clone();
+        current = end_meet;
         while current != *end {
             let mut display = current.clone();
             display.direction = display.direction.reverse();
@@ -191,7 +226,7 @@ impl DBFS {
         // 可视化最终路径
         let mut step_count = 0;
         for p in &path {
-            self.map.set_cell(p.x, p.y, 5);
+            self.map.set_cell(p.x, p.y, CellState::Solution);
             step_count += 1;
             steps.push(Point::full(
                 p.x, p.y,
@@ -223,6 +258,11 @@ impl DBFS {
     pub fn map_mut(&mut self) -> &mut Map {
         &mut self.map
     }
+    
+    /// 取出搜索后的地图
+    pub fn into_map(self) -> Map {
+        self.map
+    }
 }
 
 /// 执行 DBFS 搜索的便捷函数
@@ -245,4 +285,63 @@ mod tests {
         let result = dbfs_search(&mut map, sx, sy, ex, ey);
         println!("DBFS found: {}, path length: {}", result.found, result.path_length);
     }
+    
+    #[test]
+    fn test_dbfs_meets_at_unexpanded_endpoint() {
+        // 单格宽的走廊中正向队列始终不长于反向队列，正向搜索一直走到终点而终点侧从未扩展
+        let mut map = Map::from_text("#######\n#S...G#\n#######\n", 70, 30).unwrap();
+        let result = dbfs_search(&mut map, 1, 1, 5, 1);
+        assert!(result.found);
+        assert_eq!(result.path_length, 4);
+        assert_eq!(result.path.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>(), [(1, 1), (2, 1), (3, 1), (4, 1), (5, 1)]);
+    }
+
+    #[test]
+    fn test_dbfs_weighted_path_cost() {
+        // 唯一通路 S → . → 9 → . → G，代价为 1 + 9 + 1 + 1
+        let mut map = Map::from_text("#######\n#S.9.G#\n#######\n", 70, 30).unwrap();
+        let reverse: Vec<(i32, i32, i32)> = map.predecessors(3, 1).iter().map(|e| (e.x, e.y, e.cost)).collect();
+        assert!(reverse.iter().all(|&(_, _, cost)| cost == 9), "{:?}", reverse);
+        let result = dbfs_search(&mut map, 1, 1, 5, 1);
+        assert!(result.found);
+        assert_eq!(result.path_length, 12);
+        assert_eq!(result.path.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>(), [(1, 1), (2, 1), (3, 1), (4, 1), (5, 1)]);
+    }
+
+    #[test]
+    fn test_dbfs_matches_bfs_hops_with_portals() {
+        use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+        use crate::map::Topology;
+        for seed in 0..300 {
+            let mut map = Map::seeded(Topology::Square, 21, 21, seed, 210, 210).unwrap();
+            let mut cells = map.cell_coords();
+            cells.retain(|&c| c != map.start() && c != map.end());
+            cells.shuffle(&mut StdRng::seed_from_u64(seed));
+            for pair in cells.chunks_exact(2).take(4) {
+                map.add_portal(pair[0], pair[1], 3);
+            }
+            let ((sx, sy), (ex, ey)) = (map.start(), map.end());
+            let bfs = crate::bfs_search(&mut map.clone(), sx, sy, ex, ey);
+            let dbfs = dbfs_search(&mut map, sx, sy, ex, ey);
+            assert!(bfs.found && dbfs.found);
+            assert_eq!(dbfs.path.len(), bfs.path.len(), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_dbfs_respects_one_way_doors() {
+        // 上方捷径中的 (5, 1) 为只能向左穿过的单向门，正向必须绕下方。起点一侧的房间使正向队列
+        // 较长，反向搜索因此先沿捷径扩展；若忽略门的方向，会经过 (5, 1) 与正向搜索相遇
+        let text = "#########\n#S.....G#\n#....##.#\n#....##.#\n#.......#\n#########\n";
+        let mut map = Map::from_text(text, 90, 60).unwrap();
+        map.add_door(5, 1, Direction::Left);
+        let (sx, sy) = map.start();
+        let (ex, ey) = map.end();
+        let bfs = crate::bfs_search(&mut map.clone(), sx, sy, ex, ey);
+        let dbfs = dbfs_search(&mut map.clone(), sx, sy, ex, ey);
+        assert_eq!((dbfs.found, dbfs.path_length, dbfs.path.len()), (bfs.found, bfs.path_length, bfs.path.len()));
+        assert_eq!(dbfs.path_length, 12);
+        // 路径上的每一步都是合法的正向移动
+        assert!(dbfs.path.windows(2).all(|w| map.edge_cost(w[0].x, w[0].y, w[1].x, w[1].y).is_some()));
+    }
 }
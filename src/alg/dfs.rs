@@ -1,22 +1,20 @@
 //! DFS (深度优先搜索) 算法模块
 
-use crate::map::Map;
+use std::time::Instant;
+use crate::map::{CellState, Map};
 use crate::point::{Point, Direction, Color};
-
-/// 方向偏移量: (dx, dy, direction)
-const DIRECTIONS: [(i32, i32, Direction); 4] = [
-    (0, 1, Direction::Down),
-    (1, 0, Direction::Right),
-    (0, -1, Direction::Up),
-    (-1, 0, Direction::Left),
-];
+use super::{SearchLimits, SearchStats, SearchStatus};
 
 /// DFS 搜索结果
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DfsResult {
     pub found: bool,
     pub steps: Vec<Point>,
     pub path_length: i32,
+    pub path: Vec<Point>,
+    pub stats: SearchStats,
+    pub status: SearchStatus,
 }
 
 /// DFS 搜索器
@@ -26,16 +24,24 @@ pub struct DFS {
     sy: i32,
     ex: i32,
     ey: i32,
+    limits: SearchLimits,
 }
 
 impl DFS {
     /// 创建新的 DFS 搜索器
     pub fn new(map: Map, sx: i32, sy: i32, ex: i32, ey: i32) -> Self {
-        Self { map, sx, sy, ex, ey }
+        Self { map, sx, sy, ex, ey, limits: SearchLimits::default() }
+    }
+    
+    /// 设置搜索限制（扩展节点预算、截止时间、取消标志）
+    pub fn with_limits(mut self, limits: SearchLimits) -> Self {
+        self.limits = limits;
+        self
     }
     
     /// 执行搜索（返回所有步骤，用于可视化）
     pub fn search(&mut self) -> DfsResult {
+        let begin = Instant::now();
         let mut steps = Vec::new();
         let mut stack = Vec::new();
         
@@ -43,13 +49,24 @@ impl DFS {
         let end = Point::new(self.ex, self.ey);
         
         stack.push(start.clone());
-        self.map.set_cell(self.sx, self.sy, 4);
+        self.map.set_cell(self.sx, self.sy, CellState::Start);
         steps.push(start.clone());
         
-        let mut found = false;
+        let mut status = SearchStatus::Unreachable;
         let mut final_step = 0;
+        let mut stats = SearchStats { nodes_generated: 1, max_frontier: 1, ..Default::default() };
+        // 栈顶节点是否刚入栈、尚未检查过邻居（回溯后重新检查栈顶属于同一次扩展）
+        let mut fresh = true;
         
         while let Some(current) = stack.last().cloned() {
+            if let Some(stopped) = self.limits.check(stats.nodes_expanded) {
+                status = stopped;
+                break;
+            }
+            if fresh {
+                stats.nodes_expanded += 1;
+                fresh = false;
+            }
             steps.push(current.clone());
             
             if current == end {
@@ -57,27 +74,29 @@ impl DFS {
                 end_point.color = Color::YELLOW;
                 end_point.direction = Direction::None;
                 steps.push(end_point);
-                found = true;
+                status = SearchStatus::Found;
                 final_step = current.step;
                 break;
             }
             
             // 查找下一个可访问的邻居
             let mut found_next = false;
-            for (dx, dy, dir) in DIRECTIONS.iter() {
-                let nx = current.x + dx;
-                let ny = current.y + dy;
+            for edge in self.map.neighbors(current.x, current.y) {
+                let (nx, ny) = (edge.x, edge.y);
                 
-                if self.map.in_bounds(nx, ny) && self.map.get_cell(nx, ny) == 0 {
+                if self.map.get_cell(nx, ny) == CellState::Path {
                     let next = Point::full(
                         nx, ny, 
-                        current.step + 1, 
+                        current.step + edge.cost, 
                         0,
-                        *dir, 
+                        edge.direction, 
                         Color::SKY_BLUE
                     );
                     stack.push(next);
-                    self.map.set_cell(nx, ny, 2);
+                    fresh = true;
+                    stats.nodes_generated += 1;
+                    stats.max_frontier = stats.max_frontier.max(stack.len());
+                    self.map.set_cell(nx, ny, CellState::Visited);
                     found_next = true;
                     break;
                 }
@@ -90,15 +109,23 @@ impl DFS {
                 backtrack.step = current.step - 1;
                 backtrack.direction = Direction::None;
                 backtrack.color = Color::LIGHT_RED;
-                self.map.set_cell(current.x, current.y, 3);
+                self.map.set_cell(current.x, current.y, CellState::Backtrack);
                 steps.push(backtrack);
             }
         }
         
+        // 找到终点时栈中即为从起点到终点的路径
+        let found = status == SearchStatus::Found;
+        let path = if found { stack } else { Vec::new() };
+        stats.elapsed = begin.elapsed();
+        
         DfsResult {
             found,
             steps,
             path_length: final_step,
+            path,
+            stats,
+            status,
         }
     }
     
@@ -111,6 +138,11 @@ impl DFS {
     pub fn map_mut(&mut self) -> &mut Map {
         &mut self.map
     }
+    
+    /// 取出搜索后的地图
+    pub fn into_map(self) -> Map {
+        self.map
+    }
 }
 
 /// 执行 DFS 搜索的便捷函数
@@ -0,0 +1,108 @@
+//! 搜索限制 - 扩展节点预算、截止时间和外部取消标志
+//!
+//! 各算法在每次扩展节点前调用 `SearchLimits::check`，超出限制时立即停止并返回部分结果
+//! （已记录的步骤和统计，`found` 为 false），`SearchStatus` 说明停止的原因。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// 搜索限制，默认不限制
+#[derive(Debug, Clone, Default)]
+pub struct SearchLimits {
+    pub max_expansions: Option<usize>,   // 最多扩展的节点数
+    pub deadline: Option<Instant>,       // 截止时间
+    pub cancel: Option<Arc<AtomicBool>>, // 置为 true 时取消搜索（可在其他线程中设置）
+}
+
+/// 搜索结束的原因
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SearchStatus {
+    Found,
+    #[default]
+    Unreachable,
+    BudgetExhausted,
+    TimedOut,
+    Cancelled,
+}
+
+impl SearchLimits {
+    /// 限制扩展节点数
+    pub fn with_max_expansions(mut self, max_expansions: usize) -> Self {
+        self.max_expansions = Some(max_expansions);
+        self
+    }
+
+    /// 限制搜索时长（从调用时开始计算）
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Some(Instant::now() + timeout);
+        self
+    }
+
+    /// 设置截止时间
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// 设置取消标志
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// 已扩展 nodes_expanded 个节点时是否应停止，返回停止原因
+    pub fn check(&self, nodes_expanded: usize) -> Option<SearchStatus> {
+        if self.cancel.as_ref().is_some_and(|c| c.load(Ordering::Relaxed)) {
+            return Some(SearchStatus::Cancelled);
+        }
+        if self.max_expansions.is_some_and(|max| nodes_expanded >= max) {
+            return Some(SearchStatus::BudgetExhausted);
+        }
+        if self.deadline.is_some_and(|d| Instant::now() >= d) {
+            return Some(SearchStatus::TimedOut);
+        }
+        None
+    }
+}
+
+impl SearchStatus {
+    /// 状态名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            SearchStatus::Found => "已找到",
+            SearchStatus::Unreachable => "不可达",
+            SearchStatus::BudgetExhausted => "超出扩展预算",
+            SearchStatus::TimedOut => "超时",
+            SearchStatus::Cancelled => "已取消",
+        }
+    }
+
+    /// 是否因限制提前停止（结果为部分结果）
+    pub fn is_partial(&self) -> bool {
+        matches!(self, SearchStatus::BudgetExhausted | SearchStatus::TimedOut | SearchStatus::Cancelled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_limits() {
+        assert_eq!(SearchLimits::default().check(usize::MAX), None);
+
+        let limits = SearchLimits::default().with_max_expansions(10);
+        assert_eq!(limits.check(9), None);
+        assert_eq!(limits.check(10), Some(SearchStatus::BudgetExhausted));
+
+        assert_eq!(SearchLimits::default().with_timeout(Duration::ZERO).check(0), Some(SearchStatus::TimedOut));
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        let limits = limits.with_cancel(cancel.clone());
+        assert_eq!(limits.check(0), None);
+        cancel.store(true, Ordering::Relaxed);
+        assert_eq!(limits.check(0), Some(SearchStatus::Cancelled));
+    }
+}
@@ -2,24 +2,59 @@ pub mod dfs;
 pub mod bfs;
 pub mod dbfs;
 pub mod astar;
+pub mod pbfs;
+pub mod batch;
+pub mod limits;
 
 pub use dfs::{DFS, DfsResult, dfs_search};
 pub use bfs::{BFS, BfsResult, bfs_search};
 pub use dbfs::{DBFS, DbfsResult, dbfs_search};
 pub use astar::{AStar, AStarResult, astar_search};
+pub use pbfs::{ParallelBFS, ParallelBfsResult, parallel_bfs_search};
+pub use batch::{SolveJob, solve_batch, solve_batch_with};
+pub use limits::{SearchLimits, SearchStatus};
 
-use crate::{Map, Point};
+use std::time::Duration;
+use crate::{Map, MazeError, Point};
 
 /// 搜索算法类型
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Algorithm {
     DFS,
     BFS,
     DBFS,
     AStar,
+    ParallelBFS,
+}
+
+/// 搜索统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchStats {
+    pub nodes_expanded: usize,  // 扩展（出队 / 出栈并检查邻居）的节点数
+    pub nodes_generated: usize, // 加入待扩展集合的节点数（含起点）
+    pub max_frontier: usize,    // 待扩展集合（队列 / 栈 / 优先队列）的峰值大小
+    pub reopened: usize,        // 已扩展后又因更短路径重新加入的节点数（仅 A*）
+    pub elapsed: Duration,      // 算法本身的耗时（不含地图克隆）
+}
+
+/// 各算法统一的搜索结果
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchResult {
+    pub found: bool,
+    pub steps: Vec<Point>,
+    pub path_length: i32,
+    pub path: Vec<Point>,
+    pub stats: SearchStats,
+    pub status: SearchStatus,
 }
 
 impl Algorithm {
+    /// 全部算法（与 GUI 中的排列顺序一致）
+    pub const ALL: [Algorithm; 5] = [Algorithm::DFS, Algorithm::BFS, Algorithm::DBFS, Algorithm::AStar, Algorithm::ParallelBFS];
+
     /// 获取算法名称
     pub fn name(&self) -> &'static str {
         match self {
@@ -27,6 +62,7 @@ impl Algorithm {
             Algorithm::BFS => "BFS (广度优先搜索)",
             Algorithm::DBFS => "DBFS (双向广度优先搜索)",
             Algorithm::AStar => "A* (A-Star 启发式搜索)",
+            Algorithm::ParallelBFS => "并行 BFS (层同步广度优先搜索)",
         }
     }
     
@@ -37,30 +73,131 @@ impl Algorithm {
             Algorithm::BFS => "BFS",
             Algorithm::DBFS => "DBFS",
             Algorithm::AStar => "A*",
+            Algorithm::ParallelBFS => "PBFS",
         }
     }
 }
 
+macro_rules! impl_from_result {
+    ($($result:ty),*) => {$(
+        impl From<$result> for SearchResult {
+            fn from(r: $result) -> Self {
+                Self { found: r.found, steps: r.steps, path_length: r.path_length, path: r.path, stats: r.stats, status: r.status }
+            }
+        }
+    )*};
+}
+
+impl_from_result!(DfsResult, BfsResult, DbfsResult, AStarResult, ParallelBfsResult);
+
+/// 在地图的起点、终点之间运行指定算法
+pub fn solve(map: &mut Map, alg: Algorithm) -> SearchResult {
+    let (start, goal) = (map.start(), map.end());
+    solve_between(map, alg, start, goal)
+}
+
+/// 在任意两点之间运行指定算法
+pub fn solve_between(map: &mut Map, alg: Algorithm, start: (i32, i32), goal: (i32, i32)) -> SearchResult {
+    solve_limited(map, alg, start, goal, &SearchLimits::default())
+}
+
+/// 在任意两点之间运行指定算法，超出限制时提前停止并返回部分结果
+pub fn solve_limited(
+    map: &mut Map,
+    alg: Algorithm,
+    (sx, sy): (i32, i32),
+    (ex, ey): (i32, i32),
+    limits: &SearchLimits,
+) -> SearchResult {
+    macro_rules! run {
+        ($searcher:ident) => {{
+            let mut searcher = $searcher::new(map.clone(), sx, sy, ex, ey).with_limits(limits.clone());
+            let result = searcher.search().into();
+            *map = searcher.into_map();
+            result
+        }};
+    }
+    match alg {
+        Algorithm::DFS => run!(DFS),
+        Algorithm::BFS => run!(BFS),
+        Algorithm::DBFS => run!(DBFS),
+        Algorithm::AStar => run!(AStar),
+        Algorithm::ParallelBFS => run!(ParallelBFS),
+    }
+}
+
+/// 在地图的起点、终点之间运行指定算法，起点或终点越界、为墙时返回错误
+pub fn try_solve(map: &mut Map, alg: Algorithm) -> Result<SearchResult, MazeError> {
+    let (start, goal) = (map.start(), map.end());
+    try_solve_limited(map, alg, start, goal, &SearchLimits::default())
+}
+
+/// 检查起点、终点后运行 `solve_limited`，从而区分无效输入与确实不可达的终点
+pub fn try_solve_limited(
+    map: &mut Map,
+    alg: Algorithm,
+    start: (i32, i32),
+    goal: (i32, i32),
+    limits: &SearchLimits,
+) -> Result<SearchResult, MazeError> {
+    map.check_endpoints(start, goal)?;
+    Ok(solve_limited(map, alg, start, goal, limits))
+}
+
 /// 运行指定算法并返回步骤、是否找到、路径长度
 pub fn run_algorithm(map: &mut Map, alg: Algorithm) -> (Vec<Point>, bool, i32) {
-    let (sx, sy) = map.start();
-    let (ex, ey) = map.end();
-    match alg {
-        Algorithm::DFS => {
-            let r = dfs_search(map, sx, sy, ex, ey);
-            (r.steps, r.found, r.path_length)
-        }
-        Algorithm::BFS => {
-            let r = bfs_search(map, sx, sy, ex, ey);
-            (r.steps, r.found, r.path_length)
-        }
-        Algorithm::DBFS => {
-            let r = dbfs_search(map, sx, sy, ex, ey);
-            (r.steps, r.found, r.path_length)
+    let r = solve(map, alg);
+    (r.steps, r.found, r.path_length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_all_algorithms() {
+        let map = Map::with_size(20, 20, 200, 200);
+        for alg in Algorithm::ALL {
+            let result = solve(&mut map.clone(), alg);
+            assert!(result.found, "{}", alg.short_name());
+            assert_eq!(result.status, SearchStatus::Found);
+            assert!(result.stats.nodes_expanded > 0);
+            assert!(result.stats.nodes_generated >= result.stats.nodes_expanded);
+            assert!(result.stats.max_frontier <= result.stats.nodes_generated);
+            assert_eq!(result.path.first().map(|p| (p.x, p.y)), Some(map.start()));
+            assert_eq!(result.path.last().map(|p| (p.x, p.y)), Some(map.end()));
         }
-        Algorithm::AStar => {
-            let r = astar_search(map, sx, sy, ex, ey);
-            (r.steps, r.found, r.path_length)
+    }
+
+    #[test]
+    fn test_try_solve_rejects_invalid_endpoints() {
+        let mut map = Map::with_size(10, 10, 100, 100);
+        let (start, goal) = (map.start(), map.end());
+        let limits = SearchLimits::default();
+        assert!(try_solve(&mut map.clone(), Algorithm::BFS).is_ok_and(|r| r.status == SearchStatus::Found));
+        assert_eq!(
+            try_solve_limited(&mut map.clone(), Algorithm::BFS, start, (0, 0), &limits).err(),
+            Some(MazeError::OutOfBounds { x: 0, y: 0 })
+        );
+        map.set_cell(goal.0, goal.1, crate::CellState::Wall);
+        assert_eq!(try_solve(&mut map, Algorithm::AStar).err(), Some(MazeError::GoalBlocked { x: goal.0, y: goal.1 }));
+    }
+
+    #[test]
+    fn test_solve_limited_returns_partial_result() {
+        let map = Map::with_size(40, 40, 400, 400);
+        let (start, goal) = (map.start(), map.end());
+        for alg in Algorithm::ALL {
+            let limits = SearchLimits::default().with_max_expansions(5);
+            let result = solve_limited(&mut map.clone(), alg, start, goal, &limits);
+            assert_eq!(result.status, SearchStatus::BudgetExhausted, "{}", alg.short_name());
+            assert!(!result.found && result.path.is_empty());
+            assert!(!result.steps.is_empty());
+
+            let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+            let result = solve_limited(&mut map.clone(), alg, start, goal, &SearchLimits::default().with_cancel(cancel));
+            assert_eq!(result.status, SearchStatus::Cancelled);
+            assert_eq!(result.stats.nodes_expanded, 0);
         }
     }
 }
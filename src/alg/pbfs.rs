@@ -0,0 +1,275 @@
+//! 并行 BFS (层同步广度优先搜索) 算法模块
+//!
+//! 每一层的前沿由 rayon 并行扩展：邻居的前驱保存在原子数组中，多个前沿节点同时到达
+//! 同一格子时用 `fetch_min` 保留下标最小的前驱，因此结果与线程调度无关、每次运行相同。
+//! 层与层之间串行地标记地图并记录步骤，按层数得到与 `BFS` 相同的最短步数。
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+use rayon::prelude::*;
+use crate::map::{CellState, Map};
+use crate::point::{Point, Direction, Color};
+use super::{SearchLimits, SearchStats, SearchStatus};
+
+/// 并行 BFS 搜索结果
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParallelBfsResult {
+    pub found: bool,
+    pub steps: Vec<Point>,
+    pub path_length: i32,
+    pub path: Vec<Point>,
+    pub stats: SearchStats,
+    pub status: SearchStatus,
+}
+
+/// 并行 BFS 搜索器
+pub struct ParallelBFS {
+    map: Map,
+    sx: i32,
+    sy: i32,
+    ex: i32,
+    ey: i32,
+    limits: SearchLimits,
+    record_steps: bool, // 是否记录搜索步骤（超大网格上可关闭以节省内存）
+}
+
+/// 未访问格子的前驱
+const UNVISITED: usize = usize::MAX;
+
+impl ParallelBFS {
+    /// 创建新的并行 BFS 搜索器
+    pub fn new(map: Map, sx: i32, sy: i32, ex: i32, ey: i32) -> Self {
+        Self { map, sx, sy, ex, ey, limits: SearchLimits::default(), record_steps: true }
+    }
+
+    /// 设置搜索限制（扩展节点预算、截止时间、取消标志）
+    pub fn with_limits(mut self, limits: SearchLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// 设置是否记录搜索步骤；关闭后 `steps` 为空，只返回路径和统计
+    pub fn with_record_steps(mut self, record_steps: bool) -> Self {
+        self.record_steps = record_steps;
+        self
+    }
+
+    /// 执行搜索（默认返回所有步骤，用于可视化；同一层内的步骤按坐标排序）
+    pub fn search(&mut self) -> ParallelBfsResult {
+        let begin = Instant::now();
+        // 端点不在地图内时无法建立下标，与其他算法一样视为不可达
+        if !self.map.in_bounds(self.sx, self.sy) || !self.map.in_bounds(self.ex, self.ey) {
+            return ParallelBfsResult {
+                found: false,
+                steps: Vec::new(),
+                path_length: 0,
+                path: Vec::new(),
+                stats: SearchStats { elapsed: begin.elapsed(), ..Default::default() },
+                status: SearchStatus::Unreachable,
+            };
+        }
+        // 与 `Grid::offset` 相同的行优先下标（闭包不借用地图，搜索中仍可修改地图）
+        let width = self.map.grid.width();
+        let index = |x: i32, y: i32| y as usize * width + x as usize;
+        let coord = |i: usize| ((i % width) as i32, (i / width) as i32);
+
+        let mut steps = Vec::new();
+        let mut stats = SearchStats { nodes_generated: 1, max_frontier: 1, ..Default::default() };
+        let parent: Vec<AtomicUsize> = (0..self.map.grid.cells().len()).map(|_| AtomicUsize::new(UNVISITED)).collect();
+        let (start, end) = (index(self.sx, self.sy), index(self.ex, self.ey));
+
+        parent[start].store(start, Ordering::Relaxed);
+        self.map.set_cell(self.sx, self.sy, CellState::Start);
+        if self.record_steps {
+            steps.push(Point::with_color(self.sx, self.sy, Color::YELLOW));
+        }
+
+        let mut frontier = vec![start];
+        let mut level = 0;
+        let mut stopped = None;
+        while !frontier.is_empty() && parent[end].load(Ordering::Relaxed) == UNVISITED {
+            // 以层为单位检查限制，扩展预算可能超出不到一层的节点数
+            stopped = self.limits.check(stats.nodes_expanded);
+            if stopped.is_some() {
+                break;
+            }
+            stats.nodes_expanded += frontier.len();
+            level += 1;
+            let map = &self.map;
+            let parent = &parent;
+            // 每个工作线程复用一个邻接边缓冲区，扩展节点时不再逐个分配
+            let mut next: Vec<usize> = frontier.par_iter()
+                .fold(|| (Vec::new(), Vec::new()), |(mut edges, mut found), &u| {
+                    let (x, y) = coord(u);
+                    map.neighbors_into(x, y, &mut edges);
+                    for edge in &edges {
+                        let v = index(edge.x, edge.y);
+                        // 之前各层访问过的格子已标记为非通道；同层内先到者把 v 加入下一层
+                        if map.get_cell(edge.x, edge.y) == CellState::Path
+                            && parent[v].fetch_min(u, Ordering::Relaxed) == UNVISITED
+                        {
+                            found.push(v);
+                        }
+                    }
+                    (edges, found)
+                })
+                .flat_map_iter(|(_, found)| found)
+                .collect();
+            next.sort_unstable();
+
+            for &v in &next {
+                let (x, y) = coord(v);
+                self.map.set_cell(x, y, CellState::Visited);
+                if self.record_steps {
+                    let (px, py) = coord(parent[v].load(Ordering::Relaxed));
+                    let direction = self.direction(px, py, x, y);
+                    steps.push(Point::full(x, y, level, 0, direction, Color::SKY_BLUE));
+                }
+            }
+            stats.nodes_generated += next.len();
+            stats.max_frontier = stats.max_frontier.max(next.len());
+            frontier = next;
+        }
+
+        let found = parent[end].load(Ordering::Relaxed) != UNVISITED;
+        let status = match stopped {
+            Some(stopped) => stopped,
+            None if found => SearchStatus::Found,
+            None => SearchStatus::Unreachable,
+        };
+        let mut path = Vec::new();
+        if found {
+            // 从终点沿前驱回溯
+            let mut cells = vec![end];
+            while let Some(&v) = cells.last().filter(|&&v| v != start) {
+                cells.push(parent[v].load(Ordering::Relaxed));
+            }
+            cells.reverse();
+            for (i, &v) in cells.iter().enumerate() {
+                let (x, y) = coord(v);
+                let direction = cells.get(i + 1).map_or(Direction::None, |&next| {
+                    let (nx, ny) = coord(next);
+                    self.direction(x, y, nx, ny)
+                });
+                path.push(Point::full(x, y, i as i32, 0, direction, Color::WHITE));
+            }
+
+            // 与 BFS 相同的回溯可视化：终点、终点到起点的前驱、起点
+            for pair in path.windows(2).rev() {
+                self.map.set_cell(pair[1].x, pair[1].y, CellState::Backtrack);
+            }
+            if self.record_steps {
+                steps.push(Point::with_color(self.ex, self.ey, Color::YELLOW));
+                for pair in path.windows(2).rev() {
+                    let mut prev = pair[0].clone();
+                    prev.color = Color::LIGHT_RED;
+                    prev.direction = prev.direction.reverse();
+                    steps.push(prev);
+                }
+                steps.push(Point::with_color(self.sx, self.sy, Color::YELLOW));
+            }
+        }
+
+        // 路径代价（传送门、地形等边的代价可能不为 1）
+        let path_length = path.windows(2)
+            .map(|w| self.map.edge_cost(w[0].x, w[0].y, w[1].x, w[1].y).unwrap_or(1))
+            .sum();
+        stats.elapsed = begin.elapsed();
+
+        ParallelBfsResult {
+            found,
+            steps,
+            path_length,
+            path,
+            stats,
+            status,
+        }
+    }
+
+    /// 从 (x, y) 走到相邻格子 (nx, ny) 的方向
+    fn direction(&self, x: i32, y: i32, nx: i32, ny: i32) -> Direction {
+        self.map.neighbors(x, y)
+            .into_iter()
+            .find(|e| (e.x, e.y) == (nx, ny))
+            .map_or(Direction::None, |e| e.direction)
+    }
+
+    /// 获取地图引用
+    pub fn map(&self) -> &Map {
+        &self.map
+    }
+
+    /// 获取可变地图引用
+    pub fn map_mut(&mut self) -> &mut Map {
+        &mut self.map
+    }
+
+    /// 取出搜索后的地图
+    pub fn into_map(self) -> Map {
+        self.map
+    }
+}
+
+/// 执行并行 BFS 搜索的便捷函数
+pub fn parallel_bfs_search(map: &mut Map, sx: i32, sy: i32, ex: i32, ey: i32) -> ParallelBfsResult {
+    let mut pbfs = ParallelBFS::new(map.clone(), sx, sy, ex, ey);
+    let result = pbfs.search();
+    *map = pbfs.map;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::Topology;
+
+    #[test]
+    fn test_parallel_bfs_matches_bfs() {
+        for (topology, seed) in [(Topology::Square, 1), (Topology::Torus, 2), (Topology::Hex, 3)] {
+            let map = Map::seeded(topology, 40, 30, seed, 0, 0).unwrap();
+            let (sx, sy) = map.start();
+            let (ex, ey) = map.end();
+            let bfs = crate::bfs_search(&mut map.clone(), sx, sy, ex, ey);
+            let pbfs = parallel_bfs_search(&mut map.clone(), sx, sy, ex, ey);
+            assert!(pbfs.found);
+            assert_eq!(pbfs.path.len(), bfs.path.len());
+            assert_eq!(pbfs.path_length, bfs.path_length);
+        }
+
+        // 多次运行结果相同
+        let map = Map::seeded(Topology::Square, 60, 60, 9, 0, 0).unwrap();
+        let (sx, sy) = map.start();
+        let (ex, ey) = map.end();
+        let a = parallel_bfs_search(&mut map.clone(), sx, sy, ex, ey);
+        let b = parallel_bfs_search(&mut map.clone(), sx, sy, ex, ey);
+        let cells = |r: &ParallelBfsResult| r.steps.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+        assert_eq!(cells(&a), cells(&b));
+        assert_eq!(a.stats.nodes_expanded, b.stats.nodes_expanded);
+        assert_eq!(a.stats.nodes_generated, b.stats.nodes_generated);
+    }
+
+    #[test]
+    fn test_parallel_bfs_without_steps() {
+        let map = Map::try_open_grid(101, 101, 0, 0).unwrap();
+        let (sx, sy) = map.start();
+        let (ex, ey) = map.end();
+        let full = ParallelBFS::new(map.clone(), sx, sy, ex, ey).search();
+        let bare = ParallelBFS::new(map, sx, sy, ex, ey).with_record_steps(false).search();
+        assert!(bare.found && bare.steps.is_empty());
+        assert!(full.steps.len() > full.stats.nodes_generated);
+        assert_eq!(bare.path, full.path);
+        assert_eq!(bare.path_length, 196);
+        assert_eq!(bare.stats.nodes_expanded, full.stats.nodes_expanded);
+    }
+
+    #[test]
+    fn test_parallel_bfs_out_of_bounds_endpoints() {
+        let map = Map::seeded(Topology::Square, 11, 11, 4, 0, 0).unwrap();
+        for (start, goal) in [((1, 1), (50, 50)), ((-3, 1), (9, 9)), ((1, 1), (1, -1))] {
+            let result = crate::solve_between(&mut map.clone(), crate::Algorithm::ParallelBFS, start, goal);
+            assert!(!result.found);
+            assert_eq!(result.status, SearchStatus::Unreachable);
+        }
+    }
+}
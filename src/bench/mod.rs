@@ -0,0 +1,252 @@
+//! 基准测试模块 - 在批量生成的迷宫上比较各算法
+//!
+//! 对每种拓扑、每个尺寸以连续的种子生成 N 个迷宫，在每个迷宫上运行所有算法，
+//! 以 `SearchStats::elapsed` 计时（只含算法本身，不含迷宫生成和动画），再按 (拓扑, 尺寸, 算法) 汇总为 CSV。
+//! 完美迷宫的前沿很窄，并行 BFS 难以获益；`open` 模式改用没有内墙的开阔网格，前沿随层数线性增长，
+//! 汇总中的 `speedup_vs_bfs` 给出各算法相对 BFS 的加速比。
+
+use std::fmt::Write as _;
+use std::time::Duration;
+use crate::alg::{Algorithm, solve};
+use crate::core::error::MazeError;
+use crate::map::{Map, Topology};
+
+/// 基准测试参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchConfig {
+    pub topologies: Vec<Topology>,  // 迷宫生成器（网格拓扑）
+    pub sizes: Vec<(i32, i32)>,     // 迷宫尺寸，含义同 `Map::seeded`
+    pub mazes: usize,               // 每种拓扑、尺寸生成的迷宫数
+    pub seed: u64,                  // 第一个迷宫的种子，之后依次加一
+    pub algorithms: Vec<Algorithm>, // 参与比较的算法
+    pub open: bool,                 // 使用没有内墙的开阔网格代替迷宫（只支持方形网格，忽略种子）
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            topologies: vec![Topology::Square],
+            sizes: vec![(21, 21), (51, 51), (101, 101)],
+            mazes: 20,
+            seed: 0,
+            algorithms: Algorithm::ALL.to_vec(),
+            open: false,
+        }
+    }
+}
+
+/// 单次运行的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchSample {
+    pub topology: Topology,
+    pub size: (i32, i32),
+    pub seed: u64,
+    pub algorithm: Algorithm,
+    pub found: bool,
+    pub path_length: i32,
+    pub nodes_expanded: usize,
+    pub max_frontier: usize,
+    pub elapsed: Duration,
+}
+
+/// 同一 (拓扑, 尺寸, 算法) 下全部运行的汇总
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchSummary {
+    pub topology: Topology,
+    pub size: (i32, i32),
+    pub algorithm: Algorithm,
+    pub runs: usize,
+    pub found: usize,
+    pub mean_ms: f64,
+    pub median_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub mean_nodes_expanded: f64,
+    pub mean_max_frontier: f64,
+    pub mean_path_length: f64,
+    pub speedup_vs_bfs: Option<f64>, // 同一拓扑、尺寸下 BFS 平均耗时与本算法平均耗时之比，未运行 BFS 时为 None
+}
+
+/// 运行基准测试，返回每个迷宫、每个算法的结果
+pub fn run_bench(config: &BenchConfig) -> Result<Vec<BenchSample>, MazeError> {
+    let mut samples = Vec::new();
+    for &topology in &config.topologies {
+        if config.open && topology != Topology::Square {
+            return Err(MazeError::UnsupportedTopology(topology));
+        }
+        for &(m, n) in &config.sizes {
+            for i in 0..config.mazes as u64 {
+                let seed = config.seed.wrapping_add(i);
+                let map = if config.open {
+                    Map::try_open_grid(m, n, 0, 0)?
+                } else {
+                    Map::seeded(topology, m, n, seed, 0, 0)?
+                };
+                for &algorithm in &config.algorithms {
+                    let mut searched = map.clone();
+                    let result = solve(&mut searched, algorithm);
+                    samples.push(BenchSample {
+                        topology,
+                        size: (m, n),
+                        seed,
+                        algorithm,
+                        found: result.found,
+                        path_length: result.path_length,
+                        nodes_expanded: result.stats.nodes_expanded,
+                        max_frontier: result.stats.max_frontier,
+                        elapsed: result.stats.elapsed,
+                    });
+                }
+            }
+        }
+    }
+    Ok(samples)
+}
+
+/// 已排序数据的百分位数（最近秩法），p 取 0..=100
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// 按 (拓扑, 尺寸, 算法) 汇总，保持首次出现的顺序
+pub fn summarize(samples: &[BenchSample]) -> Vec<BenchSummary> {
+    let mut keys: Vec<(Topology, (i32, i32), Algorithm)> = Vec::new();
+    for s in samples {
+        let key = (s.topology, s.size, s.algorithm);
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    let mean_ms_of = |topology, size, algorithm| {
+        let times: Vec<f64> = samples.iter()
+            .filter(|s| (s.topology, s.size, s.algorithm) == (topology, size, algorithm))
+            .map(|s| s.elapsed.as_secs_f64() * 1000.0)
+            .collect();
+        (!times.is_empty()).then(|| times.iter().sum::<f64>() / times.len() as f64)
+    };
+    keys.into_iter()
+        .map(|(topology, size, algorithm)| {
+            let group: Vec<&BenchSample> = samples.iter()
+                .filter(|s| (s.topology, s.size, s.algorithm) == (topology, size, algorithm))
+                .collect();
+            let runs = group.len();
+            let mean = |f: &dyn Fn(&BenchSample) -> f64| group.iter().map(|s| f(s)).sum::<f64>() / runs as f64;
+            let mut times: Vec<f64> = group.iter().map(|s| s.elapsed.as_secs_f64() * 1000.0).collect();
+            times.sort_by(f64::total_cmp);
+            let mean_ms = times.iter().sum::<f64>() / runs as f64;
+            BenchSummary {
+                topology,
+                size,
+                algorithm,
+                runs,
+                found: group.iter().filter(|s| s.found).count(),
+                mean_ms,
+                median_ms: percentile(&times, 50.0),
+                p90_ms: percentile(&times, 90.0),
+                p99_ms: percentile(&times, 99.0),
+                mean_nodes_expanded: mean(&|s| s.nodes_expanded as f64),
+                mean_max_frontier: mean(&|s| s.max_frontier as f64),
+                mean_path_length: mean(&|s| s.path_length as f64),
+                speedup_vs_bfs: mean_ms_of(topology, size, Algorithm::BFS).map(|bfs| bfs / mean_ms.max(f64::EPSILON)),
+            }
+        })
+        .collect()
+}
+
+/// 汇总结果的 CSV（含表头）
+pub fn summary_csv(summaries: &[BenchSummary]) -> String {
+    let mut out = String::from(
+        "topology,width,height,algorithm,runs,found,mean_ms,median_ms,p90_ms,p99_ms,\
+         mean_nodes_expanded,mean_max_frontier,mean_path_length,speedup_vs_bfs\n",
+    );
+    for s in summaries {
+        let _ = writeln!(
+            out,
+            "{:?},{},{},{},{},{},{:.4},{:.4},{:.4},{:.4},{:.1},{:.1},{:.1},{}",
+            s.topology, s.size.0, s.size.1, s.algorithm.short_name(), s.runs, s.found,
+            s.mean_ms, s.median_ms, s.p90_ms, s.p99_ms,
+            s.mean_nodes_expanded, s.mean_max_frontier, s.mean_path_length,
+            s.speedup_vs_bfs.map_or(String::new(), |x| format!("{:.2}", x))
+        );
+    }
+    out
+}
+
+/// 每次运行的原始结果 CSV（含表头）
+pub fn samples_csv(samples: &[BenchSample]) -> String {
+    let mut out = String::from("topology,width,height,seed,algorithm,found,path_length,nodes_expanded,max_frontier,elapsed_ms\n");
+    for s in samples {
+        let _ = writeln!(
+            out,
+            "{:?},{},{},{},{},{},{},{},{},{:.4}",
+            s.topology, s.size.0, s.size.1, s.seed, s.algorithm.short_name(), s.found,
+            s.path_length, s.nodes_expanded, s.max_frontier, s.elapsed.as_secs_f64() * 1000.0
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        let data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+        assert_eq!(percentile(&data, 50.0), 5.0);
+        assert_eq!(percentile(&data, 90.0), 9.0);
+        assert_eq!(percentile(&data, 99.0), 10.0);
+        assert_eq!(percentile(&[], 50.0), 0.0);
+    }
+
+    #[test]
+    fn test_run_bench() {
+        let config = BenchConfig {
+            topologies: vec![Topology::Square, Topology::Hex],
+            sizes: vec![(11, 11)],
+            mazes: 3,
+            ..Default::default()
+        };
+        let samples = run_bench(&config).unwrap();
+        assert_eq!(samples.len(), 2 * 3 * Algorithm::ALL.len());
+        assert!(samples.iter().all(|s| s.found && s.max_frontier >= 1));
+
+        let summaries = summarize(&samples);
+        assert_eq!(summaries.len(), 2 * Algorithm::ALL.len());
+        assert!(summaries.iter().all(|s| s.runs == 3 && s.found == 3));
+        // 完美迷宫中 BFS 与 A* 的路径长度相同
+        let path = |alg| summaries.iter().find(|s| s.topology == Topology::Square && s.algorithm == alg).unwrap().mean_path_length;
+        assert_eq!(path(Algorithm::BFS), path(Algorithm::AStar));
+
+        let csv = summary_csv(&summaries);
+        assert_eq!(csv.lines().count(), summaries.len() + 1);
+        assert!(csv.lines().nth(1).unwrap().starts_with("Square,11,11,DFS,3,3,"));
+        assert!(summaries.iter().all(|s| s.speedup_vs_bfs.is_some()));
+    }
+
+    #[test]
+    fn test_open_grid_bench() {
+        let config = BenchConfig {
+            sizes: vec![(41, 41)],
+            mazes: 2,
+            algorithms: vec![Algorithm::BFS, Algorithm::ParallelBFS],
+            open: true,
+            ..Default::default()
+        };
+        let summaries = summarize(&run_bench(&config).unwrap());
+        assert_eq!(summaries.len(), 2);
+        let (bfs, pbfs) = (&summaries[0], &summaries[1]);
+        assert_eq!(bfs.speedup_vs_bfs, Some(1.0));
+        assert!(pbfs.speedup_vs_bfs.is_some());
+        // 开阔网格中曼哈顿距离即为最短步数，前沿宽度随层数增长
+        assert_eq!((bfs.mean_path_length, pbfs.mean_path_length), (76.0, 76.0));
+        assert!(pbfs.mean_max_frontier >= 30.0, "{}", pbfs.mean_max_frontier);
+
+        let hex = BenchConfig { topologies: vec![Topology::Hex], ..config };
+        assert_eq!(run_bench(&hex), Err(MazeError::UnsupportedTopology(Topology::Hex)));
+    }
+}
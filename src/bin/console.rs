@@ -0,0 +1,381 @@
+//! 迷宫寻路算法 - 命令行版本（无需图形界面）
+//!
+//! 生成或加载迷宫，运行一个或全部算法，输出路径长度、扩展节点数和耗时；
+//! 也可以在终端中以 ANSI 颜色显示结果或播放搜索过程（仅方形网格），
+//! 或以 `--bench` 在批量生成的迷宫上比较全部算法并输出 CSV。
+
+use std::fmt::Write as _;
+use std::process::ExitCode;
+use std::time::Duration;
+use maze_routing_algorithm::*;
+
+const USAGE: &str = "\
+用法: maze-console [选项]
+
+  -a, --algorithm <名称>  dfs | bfs | dbfs | astar | pbfs | all（默认 all），可用逗号分隔多个
+  -t, --topology <名称>   square | torus | hex | polar（默认 square），--bench 时可用逗号分隔多个
+  -W, --width <数值>      地图宽度，grid 坐标；六边形为单元格数（默认 56）
+  -H, --height <数值>     地图高度；极坐标为环数（默认 56）
+  -s, --seed <数值>       随机种子（默认随机，并在结果中给出）
+  -l, --load <文件>       从文本迷宫或图片（.png / .bmp / .gif）加载地图，忽略生成参数
+  -o, --output <文件>     将结果写入文件（默认输出到终端）
+  -f, --format <格式>     text | csv | json（默认 text）
+      --show              在终端中显示每个算法搜索结束后的地图（只支持 square / torus）
+      --animate           在终端中播放每个算法的搜索过程（只支持 square / torus）
+      --max-expansions <数值>  每个算法最多扩展的节点数，超出时输出部分结果
+      --timeout <毫秒>    每个算法的搜索时限，超出时输出部分结果
+      --stride <数值>     播放时每帧之间的步数（默认 10）
+      --delay <毫秒>      播放时每帧的显示时长（默认 50）
+      --bench <数量>      基准测试：每种拓扑、尺寸以 seed, seed+1, ... 生成该数量的迷宫，输出汇总 CSV
+      --sizes <列表>      基准测试的尺寸，如 21x21,51x51（默认 21x21,51x51,101x101）
+      --raw               基准测试输出每次运行的原始结果而非汇总
+      --open              基准测试使用没有内墙的开阔网格（只支持 square），用于比较 BFS 与并行 BFS
+  -h, --help              显示本帮助";
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Csv,
+    Json,
+}
+
+/// 命令行参数
+#[derive(Debug)]
+struct Args {
+    algorithms: Vec<Algorithm>,
+    topologies: Vec<Topology>,
+    width: i32,
+    height: i32,
+    seed: Option<u64>,
+    load: Option<String>,
+    output: Option<String>,
+    format: Format,
+    max_expansions: Option<usize>,
+    timeout: Option<Duration>,
+    show: bool,
+    animate: bool,
+    animation: export::AnimationOptions,
+    bench: Option<usize>,
+    sizes: Vec<(i32, i32)>,
+    raw: bool,
+    open: bool,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            algorithms: Algorithm::ALL.to_vec(),
+            topologies: vec![Topology::Square],
+            width: 56,
+            height: 56,
+            seed: None,
+            load: None,
+            output: None,
+            format: Format::Text,
+            max_expansions: None,
+            timeout: None,
+            show: false,
+            animate: false,
+            animation: export::AnimationOptions::default(),
+            bench: None,
+            sizes: BenchConfig::default().sizes,
+            raw: false,
+            open: false,
+        }
+    }
+}
+
+/// 解析命令行参数，`Ok(None)` 表示显示帮助
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut parsed = Args::default();
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--show" => {
+                parsed.show = true;
+                continue;
+            }
+            "--animate" => {
+                parsed.animate = true;
+                continue;
+            }
+            "--raw" => {
+                parsed.raw = true;
+                continue;
+            }
+            "--open" => {
+                parsed.open = true;
+                continue;
+            }
+            _ => {}
+        }
+        let value = args.next().ok_or_else(|| format!("{} 缺少参数值", flag))?;
+        let number = |v: &str| v.parse::<i32>().map_err(|_| format!("{} 需要整数: {}", flag, v));
+        match flag.as_str() {
+            "-a" | "--algorithm" => {
+                let lists = value.split(',').map(parse_algorithm).collect::<Result<Vec<_>, _>>()?;
+                parsed.algorithms = lists.concat();
+            }
+            "-t" | "--topology" => {
+                parsed.topologies = value.split(',').map(parse_topology).collect::<Result<_, _>>()?
+            }
+            "-W" | "--width" => parsed.width = number(&value)?,
+            "-H" | "--height" => parsed.height = number(&value)?,
+            "-s" | "--seed" => parsed.seed = Some(value.parse().map_err(|_| format!("无效的种子: {}", value))?),
+            "-l" | "--load" => parsed.load = Some(value),
+            "-o" | "--output" => parsed.output = Some(value),
+            "--max-expansions" => parsed.max_expansions = Some(number(&value)?.max(0) as usize),
+            "--timeout" => parsed.timeout = Some(Duration::from_millis(number(&value)?.max(0) as u64)),
+            "--stride" => parsed.animation.stride = number(&value)?.max(1) as usize,
+            "--delay" => parsed.animation.delay_ms = number(&value)?.max(0) as u32,
+            "--bench" => parsed.bench = Some(number(&value)?.max(1) as usize),
+            "--sizes" => parsed.sizes = value.split(',').map(parse_size).collect::<Result<_, _>>()?,
+            "-f" | "--format" => {
+                parsed.format = match value.to_lowercase().as_str() {
+                    "text" => Format::Text,
+                    "csv" => Format::Csv,
+                    "json" => Format::Json,
+                    _ => return Err(format!("未知的输出格式: {}", value)),
+                }
+            }
+            _ => return Err(format!("未知的选项: {}", flag)),
+        }
+    }
+    // 终端渲染只支持方形网格（加载的文件均为方形网格）
+    let renders = parsed.show || parsed.animate;
+    if let Some(&topology) = parsed.topologies.iter().find(|t| !matches!(t, Topology::Square | Topology::Torus)) {
+        if renders && parsed.load.is_none() && parsed.bench.is_none() {
+            return Err(format!("--show / --animate 只支持 square 和 torus 拓扑，不支持 {:?}", topology));
+        }
+    }
+    Ok(Some(parsed))
+}
+
+fn parse_algorithm(name: &str) -> Result<Vec<Algorithm>, String> {
+    match name.to_lowercase().as_str() {
+        "all" => Ok(Algorithm::ALL.to_vec()),
+        "dfs" => Ok(vec![Algorithm::DFS]),
+        "bfs" => Ok(vec![Algorithm::BFS]),
+        "dbfs" => Ok(vec![Algorithm::DBFS]),
+        "astar" | "a*" => Ok(vec![Algorithm::AStar]),
+        "pbfs" => Ok(vec![Algorithm::ParallelBFS]),
+        _ => Err(format!("未知的算法: {}", name)),
+    }
+}
+
+fn parse_topology(name: &str) -> Result<Topology, String> {
+    match name.to_lowercase().as_str() {
+        "square" => Ok(Topology::Square),
+        "torus" => Ok(Topology::Torus),
+        "hex" => Ok(Topology::Hex),
+        "polar" => Ok(Topology::Polar),
+        _ => Err(format!("未知的网格拓扑: {}", name)),
+    }
+}
+
+fn parse_size(text: &str) -> Result<(i32, i32), String> {
+    let invalid = || format!("无效的尺寸: {}（应为 宽x高）", text);
+    let (w, h) = text.split_once(['x', 'X']).ok_or_else(invalid)?;
+    Ok((w.trim().parse().map_err(|_| invalid())?, h.trim().parse().map_err(|_| invalid())?))
+}
+
+/// 加载或生成地图，返回地图及其描述
+fn build_map(args: &Args) -> Result<(Map, String), MazeError> {
+    if let Some(path) = &args.load {
+        let lower = path.to_lowercase();
+        let map = if [".png", ".bmp", ".gif"].iter().any(|ext| lower.ends_with(ext)) {
+            Map::load_image(path, &ImageImport::default())?
+        } else {
+            let text = std::fs::read_to_string(path).map_err(|e| MazeError::Io(e.to_string()))?;
+            Map::from_text(&text, 0, 0)?
+        };
+        return Ok((map, path.clone()));
+    }
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let topology = args.topologies[0];
+    let map = Map::seeded(topology, args.width, args.height, seed, 0, 0)?;
+    Ok((map, format!("{:?} {}×{} seed={}", topology, args.width, args.height, seed)))
+}
+
+/// 单个算法的运行结果
+struct Row {
+    algorithm: Algorithm,
+    result: SearchResult,
+}
+
+impl Row {
+    fn elapsed_ms(&self) -> f64 {
+        self.result.stats.elapsed.as_secs_f64() * 1000.0
+    }
+}
+
+/// 文本表格各列的显示宽度（最后一列不补齐）
+const COLUMN_WIDTHS: [usize; 5] = [8, 6, 12, 12, 12];
+
+/// 字符串在终端中的显示宽度（中文等非 ASCII 字符占两列）
+fn display_width(text: &str) -> usize {
+    text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum()
+}
+
+/// 按 `COLUMN_WIDTHS` 排出一行：第一列左对齐，其余右对齐，最后一列与前一列间隔两个空格
+fn table_line(cells: [&str; 6]) -> String {
+    let mut line = String::new();
+    for (i, (cell, width)) in cells.iter().zip(COLUMN_WIDTHS).enumerate() {
+        let pad = " ".repeat(width.saturating_sub(display_width(cell)));
+        if i == 0 {
+            line.push_str(cell);
+            line.push_str(&pad);
+        } else {
+            line.push_str(&pad);
+            line.push_str(cell);
+        }
+    }
+    line.push_str("  ");
+    line.push_str(cells[5]);
+    line
+}
+
+fn report(rows: &[Row], source: &str, format: Format) -> String {
+    let mut out = String::new();
+    match format {
+        Format::Text => {
+            let _ = writeln!(out, "地图: {}", source);
+            let _ = writeln!(out, "{}", table_line(["算法", "找到", "路径长度", "扩展节点", "耗时(ms)", "状态"]));
+            for row in rows {
+                let _ = writeln!(
+                    out,
+                    "{}",
+                    table_line([
+                        row.algorithm.short_name(),
+                        if row.result.found { "是" } else { "否" },
+                        &row.result.path_length.to_string(),
+                        &row.result.stats.nodes_expanded.to_string(),
+                        &format!("{:.3}", row.elapsed_ms()),
+                        row.result.status.name(),
+                    ])
+                );
+            }
+        }
+        Format::Csv => {
+            let _ = writeln!(out, "algorithm,found,path_length,nodes_expanded,elapsed_ms,status");
+            for row in rows {
+                let _ = writeln!(
+                    out,
+                    "{},{},{},{},{:.3},{:?}",
+                    row.algorithm.short_name(), row.result.found, row.result.path_length,
+                    row.result.stats.nodes_expanded, row.elapsed_ms(), row.result.status
+                );
+            }
+        }
+        Format::Json => {
+            let entries: Vec<String> = rows.iter()
+                .map(|row| format!(
+                    r#"{{"algorithm":"{}","found":{},"path_length":{},"nodes_expanded":{},"elapsed_ms":{:.3},"status":"{:?}"}}"#,
+                    row.algorithm.short_name(), row.result.found, row.result.path_length,
+                    row.result.stats.nodes_expanded, row.elapsed_ms(), row.result.status
+                ))
+                .collect();
+            let _ = writeln!(out, r#"{{"map":"{}","results":[{}]}}"#, source.replace('\\', "\\\\").replace('"', "\\\""), entries.join(","));
+        }
+    }
+    out
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    if let Some(mazes) = args.bench {
+        return run_bench(&args, mazes);
+    }
+    // 单次求解只生成一个地图，多个拓扑只在基准测试中有意义
+    if args.topologies.len() > 1 {
+        eprintln!("只有 --bench 时 -t 才能指定多个拓扑\n\n{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    let (map, source) = match build_map(&args) {
+        Ok(built) => built,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut rows = Vec::new();
+    for &algorithm in &args.algorithms {
+        let mut searched = map.clone();
+        let mut limits = SearchLimits { max_expansions: args.max_expansions, ..Default::default() };
+        if let Some(timeout) = args.timeout {
+            limits = limits.with_timeout(timeout);
+        }
+        let (start, goal) = (map.start(), map.end());
+        let result = match try_solve_limited(&mut searched, algorithm, start, goal, &limits) {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        if args.show || args.animate {
+            println!("{}", algorithm.name());
+            let shown = if args.animate {
+                export::animate_ansi(&map, &result.steps, 0, &args.animation, &mut std::io::stdout()).map(|_| ())
+            } else {
+                export::render_ansi(&searched, 0).map(|text| print!("{}", text))
+            };
+            if let Err(e) = shown {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+        rows.push(Row { algorithm, result });
+    }
+
+    write_output(&args, report(&rows, &source, args.format))
+}
+
+/// 基准测试模式
+fn run_bench(args: &Args, mazes: usize) -> ExitCode {
+    let config = BenchConfig {
+        topologies: args.topologies.clone(),
+        sizes: args.sizes.clone(),
+        mazes,
+        seed: args.seed.unwrap_or(0),
+        algorithms: args.algorithms.clone(),
+        open: args.open,
+    };
+    match bench::run_bench(&config) {
+        Ok(samples) if args.raw => write_output(args, bench::samples_csv(&samples)),
+        Ok(samples) => write_output(args, bench::summary_csv(&bench::summarize(&samples))),
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// 将结果写入 --output 指定的文件或终端
+fn write_output(args: &Args, text: String) -> ExitCode {
+    match &args.output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, text) {
+                eprintln!("{}", MazeError::Io(e.to_string()));
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", text),
+    }
+    ExitCode::SUCCESS
+}
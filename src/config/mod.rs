@@ -1,8 +1,14 @@
 use eframe::egui;
 
-pub const MAP_SIZE: i32 = 56;
+// 默认地图宽高（grid 坐标，可以不同、可以为奇数）及单元格像素大小，运行时可在左侧面板调整
+pub const MAP_WIDTH: i32 = 56;
+pub const MAP_HEIGHT: i32 = 56;
 pub const CELL_SIZE: f32 = 10.0;
 
+// 运行时可调整的范围
+pub const MAP_DIM_RANGE: std::ops::RangeInclusive<i32> = 4..=300;
+pub const CELL_SIZE_RANGE: std::ops::RangeInclusive<f32> = 2.0..=30.0;
+
 // GUI 布局尺寸
 pub const VIEWPORT_WIDTH: f32 = 1100.0;
 pub const VIEWPORT_HEIGHT: f32 = 700.0;
@@ -16,3 +22,15 @@ pub const CELL_COLORS: [egui::Color32; 6] = [
     egui::Color32::from_rgb(255, 255, 0),    // 4 - 起点/终点 (黄)
     egui::Color32::from_rgb(127, 255, 212),  // 5 - 最终路径 (青)
 ];
+
+// 传送门标记及连线颜色 (紫)
+pub const PORTAL_COLOR: egui::Color32 = egui::Color32::from_rgb(148, 0, 211);
+
+// 单向门箭头颜色 (深橙)
+pub const DOOR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 140, 0);
+
+// 楼梯及换层高亮颜色 (绿)
+pub const STAIR_COLOR: egui::Color32 = egui::Color32::from_rgb(34, 139, 34);
+
+// 遮罩外虚空颜色 (浅灰)
+pub const VOID_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
@@ -0,0 +1,49 @@
+//! 错误模块 - 地图构造与搜索中可区分的错误类型
+
+use std::fmt;
+use crate::core::map::Topology;
+
+/// 迷宫相关错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MazeError {
+    /// 尺寸无法构成该拓扑的迷宫
+    InvalidDimensions { m: i32, n: i32, reason: &'static str },
+    /// 文本迷宫格式错误（行、列从 1 开始）
+    InvalidText { line: usize, column: usize, reason: &'static str },
+    /// 图片无法读取或解码
+    InvalidImage(String),
+    /// 该操作不支持此网格拓扑
+    UnsupportedTopology(Topology),
+    /// 坐标超出地图范围
+    OutOfBounds { x: i32, y: i32 },
+    /// 楼层不在 [0, floors) 内
+    InvalidFloor { floor: i32, floors: i32 },
+    /// 搜索起点为墙
+    StartBlocked { x: i32, y: i32 },
+    /// 搜索终点为墙
+    GoalBlocked { x: i32, y: i32 },
+    /// 文件读写或编码失败
+    Io(String),
+}
+
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MazeError::InvalidDimensions { m, n, reason } => {
+                write!(f, "无效的地图尺寸 {}×{}: {}", m, n, reason)
+            }
+            MazeError::InvalidText { line, column, reason } => {
+                write!(f, "文本迷宫第 {} 行第 {} 列: {}", line, column, reason)
+            }
+            MazeError::InvalidImage(reason) => write!(f, "无法读取图片: {}", reason),
+            MazeError::UnsupportedTopology(topology) => write!(f, "不支持的网格拓扑: {:?}", topology),
+            MazeError::OutOfBounds { x, y } => write!(f, "坐标 ({}, {}) 超出地图范围", x, y),
+            MazeError::InvalidFloor { floor, floors } => write!(f, "楼层 {} 不存在（共 {} 层）", floor, floors),
+            MazeError::StartBlocked { x, y } => write!(f, "起点 ({}, {}) 是墙", x, y),
+            MazeError::GoalBlocked { x, y } => write!(f, "终点 ({}, {}) 是墙", x, y),
+            MazeError::Io(reason) => write!(f, "文件读写失败: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for MazeError {}
@@ -0,0 +1,156 @@
+//! 网格存储 - 以行优先的连续数组保存每个坐标的 `CellState`
+//!
+//! 每个格子只占一个字节，`(x, y)` 位于下标 `y * width + x`，比逐行分配的 `Vec<Vec<i32>>` 节省内存、
+//! 遍历时也更利于缓存。序列化时仍输出为按行嵌套的整数数组，与原有 JSON 格式一致。
+
+use std::ops::{Index, IndexMut};
+
+/// 单元格状态
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum CellState {
+    #[default]
+    Path = 0,       // 通道
+    Wall = 1,       // 墙
+    Visited = 2,    // 已访问（搜索中）
+    Backtrack = 3,  // 回溯（已探索）
+    Start = 4,      // 起点/终点
+    Solution = 5,   // 最终路径
+}
+
+impl CellState {
+    /// 全部状态，下标与数值相同
+    pub const ALL: [CellState; 6] = [
+        CellState::Path,
+        CellState::Wall,
+        CellState::Visited,
+        CellState::Backtrack,
+        CellState::Start,
+        CellState::Solution,
+    ];
+
+    /// 由数值得到状态，未知数值返回 None
+    pub fn from_u8(value: u8) -> Option<Self> {
+        Self::ALL.get(value as usize).copied()
+    }
+}
+
+/// 行优先存储的二维网格
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    cells: Vec<CellState>,
+}
+
+impl Grid {
+    /// 创建 width × height 的网格，所有格子为 fill
+    pub fn new(width: usize, height: usize, fill: CellState) -> Self {
+        Self { width, height, cells: vec![fill; width * height] }
+    }
+
+    /// 列数
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// 行数
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// (x, y) 在连续数组中的下标
+    #[inline]
+    pub fn offset(&self, x: usize, y: usize) -> usize {
+        debug_assert!(x < self.width && y < self.height);
+        y * self.width + x
+    }
+
+    /// 下标对应的坐标 (x, y)
+    #[inline]
+    pub fn coord(&self, offset: usize) -> (usize, usize) {
+        (offset % self.width, offset / self.width)
+    }
+
+    /// 第 y 行
+    pub fn row(&self, y: usize) -> &[CellState] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// 按行遍历
+    pub fn rows(&self) -> impl Iterator<Item = &[CellState]> {
+        // width 为 0 时 chunks_exact 会 panic，此时也没有任何格子
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    /// 全部格子（行优先）
+    pub fn cells(&self) -> &[CellState] {
+        &self.cells
+    }
+}
+
+impl Index<(usize, usize)> for Grid {
+    type Output = CellState;
+
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> &CellState {
+        &self.cells[self.offset(x, y)]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Grid {
+    #[inline]
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut CellState {
+        let i = self.offset(x, y);
+        &mut self.cells[i]
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Grid {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+        let mut seq = serializer.serialize_seq(Some(self.height))?;
+        for row in self.rows() {
+            seq.serialize_element(&row.iter().map(|&c| c as u8).collect::<Vec<u8>>())?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Grid {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        let rows = Vec::<Vec<u8>>::deserialize(deserializer)?;
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return Err(D::Error::custom("grid 各行长度不一致"));
+        }
+        let cells = rows.iter()
+            .flatten()
+            .map(|&v| CellState::from_u8(v).ok_or_else(|| D::Error::custom(format!("未知的单元格状态: {}", v))))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { width, height: rows.len(), cells })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_major_layout() {
+        let mut grid = Grid::new(3, 2, CellState::Wall);
+        grid[(2, 1)] = CellState::Path;
+        assert_eq!(grid.offset(2, 1), 5);
+        assert_eq!(grid.coord(5), (2, 1));
+        assert_eq!(grid.cells()[5], CellState::Path);
+        assert_eq!(grid.row(1), [CellState::Wall, CellState::Wall, CellState::Path]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(std::mem::size_of::<CellState>(), 1);
+        assert!(CellState::ALL.iter().all(|&c| CellState::from_u8(c as u8) == Some(c)));
+        assert_eq!(CellState::from_u8(6), None);
+    }
+}
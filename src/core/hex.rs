@@ -0,0 +1,142 @@
+//! 六边形网格模块 - 六边形拓扑的迷宫生成与邻接关系
+//!
+//! 采用 "even-r" 偏移坐标：单元格为尖顶六边形，y 为偶数的行整体向右错开半格。
+//! 所有 1..=m × 1..=n 的坐标都是单元格，墙位于相邻单元格之间，打通的墙记录在
+//! `Map::passages` 中。
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::map::{CellState, Map, Edge, Topology, UnionFind};
+use crate::point::Direction;
+use crate::core::error::MazeError;
+
+/// 六边形邻居偏移量: (dx, dy, direction)，分别对应奇数行与偶数行
+const ODD_ROW_DIRECTIONS: [(i32, i32, Direction); 6] = [
+    (1, 0, Direction::Right),
+    (0, 1, Direction::DownRight),
+    (-1, 1, Direction::DownLeft),
+    (-1, 0, Direction::Left),
+    (-1, -1, Direction::UpLeft),
+    (0, -1, Direction::UpRight),
+];
+
+const EVEN_ROW_DIRECTIONS: [(i32, i32, Direction); 6] = [
+    (1, 0, Direction::Right),
+    (1, 1, Direction::DownRight),
+    (0, 1, Direction::DownLeft),
+    (-1, 0, Direction::Left),
+    (0, -1, Direction::UpLeft),
+    (1, -1, Direction::UpRight),
+];
+
+/// 获取 y 行的六边形邻居偏移量
+pub fn hex_directions(y: i32) -> &'static [(i32, i32, Direction); 6] {
+    if y % 2 == 0 {
+        &EVEN_ROW_DIRECTIONS
+    } else {
+        &ODD_ROW_DIRECTIONS
+    }
+}
+
+/// 偏移坐标转换为轴向坐标 (q, r)
+fn to_axial(x: i32, y: i32) -> (i32, i32) {
+    (x - (y + (y & 1)) / 2, y)
+}
+
+/// 两个六边形单元格之间的最少步数
+pub fn hex_distance(x1: i32, y1: i32, x2: i32, y2: i32) -> i32 {
+    let (q1, r1) = to_axial(x1, y1);
+    let (q2, r2) = to_axial(x2, y2);
+    let dq = q1 - q2;
+    let dr = r1 - r2;
+    (dq.abs() + dr.abs() + (dq + dr).abs()) / 2
+}
+
+impl Map {
+    /// 创建 m × n 个单元格的六边形迷宫；尺寸非法时 panic
+    pub fn hex(m: i32, n: i32, width: i32, height: i32) -> Self {
+        Self::try_hex(m, n, width, height).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// 创建六边形迷宫，尺寸非法时返回错误
+    pub fn try_hex(m: i32, n: i32, width: i32, height: i32) -> Result<Self, MazeError> {
+        Self::validate_dimensions(m, n, 1, Topology::Hex)?;
+        let mut map = Self::blank(m, n, 1, Topology::Hex, width, height);
+        map.generate();
+        Ok(map)
+    }
+
+    /// Kruskal + 并查集生成六边形完美迷宫（跳过遮罩外的单元格）
+    pub(crate) fn generate_hex(&mut self, rng: &mut impl Rng) {
+
+        for (x, y) in self.cell_coords() {
+            self.grid[(x as usize, y as usize)] = CellState::Path;
+        }
+
+        // 每个单元格只收集右、右下、左下三条边，避免重复
+        let mut edges: Vec<((i32, i32), (i32, i32))> = Vec::new();
+        for (x, y) in self.cell_coords() {
+            for (dx, dy, _) in hex_directions(y).iter().take(3) {
+                let (nx, ny) = (x + dx, y + dy);
+                if self.in_bounds(nx, ny) {
+                    edges.push(((x, y), (nx, ny)));
+                }
+            }
+        }
+        edges.shuffle(rng);
+
+        let idx = |x: i32, y: i32| ((y - 1) * self.m + (x - 1)) as usize;
+        let mut uf = UnionFind::new((self.m * self.n) as usize);
+        let mut carved = Vec::new();
+        for (a, b) in edges {
+            let (ia, ib) = (idx(a.0, a.1), idx(b.0, b.1));
+            if uf.find(ia) != uf.find(ib) {
+                uf.union(ia, ib);
+                carved.push((a, b));
+            }
+        }
+        for (a, b) in carved {
+            self.open_passage(a, b);
+        }
+    }
+
+    /// 六边形网格的 6 邻域（仅包含已打通的墙）
+    pub(crate) fn hex_neighbors(&self, x: i32, y: i32, edges: &mut Vec<Edge>) {
+        let neighbors = hex_directions(y)
+            .iter()
+            .map(|(dx, dy, dir)| (x + dx, y + dy, *dir))
+            .filter(|&(nx, ny, _)| {
+                self.in_bounds(nx, ny) && self.get_cell(nx, ny) != CellState::Wall && self.has_passage((x, y), (nx, ny))
+            })
+            .map(|(nx, ny, direction)| Edge { x: nx, y: ny, direction, cost: 1 });
+        edges.extend(neighbors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_distance() {
+        assert_eq!(hex_distance(3, 3, 3, 3), 0);
+        for (dx, dy, _) in hex_directions(3).iter() {
+            assert_eq!(hex_distance(3, 3, 3 + dx, 3 + dy), 1);
+        }
+        for (dx, dy, _) in hex_directions(4).iter() {
+            assert_eq!(hex_distance(3, 4, 3 + dx, 4 + dy), 1);
+        }
+        assert_eq!(hex_distance(1, 1, 1, 5), 4);
+    }
+
+    #[test]
+    fn test_hex_maze_is_spanning_tree() {
+        let map = Map::hex(8, 6, 100, 100);
+        assert_eq!(map.passages.len(), 8 * 6 - 1);
+        let (sx, sy) = map.start();
+        let (ex, ey) = map.end();
+        let result = crate::bfs_search(&mut map.clone(), sx, sy, ex, ey);
+        assert!(result.found);
+        assert!(result.path_length >= hex_distance(sx, sy, ex, ey));
+    }
+}
@@ -1,29 +1,104 @@
 //! 地图模块 - 迷宫地图的生成与管理
 
+use std::collections::{HashMap, HashSet, hash_map::Entry};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use crate::point::Color;
+use crate::point::{Color, Direction, Point};
+use crate::core::hex::hex_distance;
+use crate::core::polar::ring_size;
+use crate::core::mask::Mask;
+use crate::core::error::MazeError;
+pub use crate::core::grid::{CellState, Grid};
 
-/// 单元格状态
+/// 方向偏移量: (dx, dy, direction)
+pub const DIRECTIONS: [(i32, i32, Direction); 4] = [
+    (0, 1, Direction::Down),
+    (1, 0, Direction::Right),
+    (0, -1, Direction::Up),
+    (-1, 0, Direction::Left),
+];
+
+/// 方形网格中坐标不超过 size - 1 的最后一个奇数（单元格）坐标
+fn last_cell(size: i32) -> i32 {
+    size - 1 - size % 2
+}
+
+/// 传送门：成对的单元格，踏上其中一端即可以指定代价到达另一端
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Portal {
+    pub a: (i32, i32),   // 一端坐标
+    pub b: (i32, i32),   // 另一端坐标
+    pub cost: i32,       // 传送代价
+}
+
+impl Portal {
+    /// 若 (x, y) 为传送门一端，返回另一端坐标
+    pub fn partner(&self, x: i32, y: i32) -> Option<(i32, i32)> {
+        if self.a == (x, y) {
+            Some(self.b)
+        } else if self.b == (x, y) {
+            Some(self.a)
+        } else {
+            None
+        }
+    }
+}
+
+/// 邻接边：从某个单元格一步可到达的单元格
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edge {
+    pub x: i32,
+    pub y: i32,
+    pub direction: Direction, // 移动方向
+    pub cost: i32,            // 移动代价
+}
+
+/// 网格拓扑
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum CellState {
-    Wall = 1,       // 墙
-    Path = 0,       // 通道
-    Visited = 2,    // 已访问（搜索中）
-    Backtrack = 3,  // 回溯（已探索）
-    Start = 4,      // 起点/终点
-    Solution = 5,   // 最终路径
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Topology {
+    Square, // 方形网格（奇数坐标为单元格，偶数坐标为墙）
+    Torus,  // 环面方形网格：左右、上下边界首尾相连（m、n 需为偶数，第 m 列 / 第 n 行为接缝处的墙）
+    Hex,    // 六边形网格（每个坐标都是单元格，墙位于相邻单元格之间）
+    Polar,  // 极坐标网格（同心环，y 为环号，x 为环内序号，墙位于相邻单元格之间）
+}
+
+impl Topology {
+    /// 单元格之间的墙是否记录在 passages 中（而不是占用 grid 中的格子）
+    pub fn uses_passages(&self) -> bool {
+        matches!(self, Topology::Hex | Topology::Polar)
+    }
 }
 
 /// 迷宫地图结构体
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::core::serialize::RawMap"))]
 pub struct Map {
     pub sx: i32,           // 起点 x
     pub sy: i32,           // 起点 y
+    pub ex: i32,           // 终点 x
+    pub ey: i32,           // 终点 y
     pub m: i32,            // 地图宽度
     pub n: i32,            // 地图高度
     pub width: i32,        // 像素宽度
     pub height: i32,       // 像素高度
-    pub grid: Vec<Vec<i32>>, // 地图数据
+    pub grid: Grid,        // 地图数据（行优先，每格一个字节）
+    pub portals: Vec<Portal>, // 传送门
+    #[cfg_attr(feature = "serde", serde(with = "crate::core::serialize::sorted_map"))]
+    pub doors: HashMap<(i32, i32), Direction>, // 单向门：只能沿指定方向穿过的单元格
+    pub floors: i32,       // 楼层数（各层在 grid 中自上而下依次排列，每层占 n + 1 行）
+    #[cfg_attr(feature = "serde", serde(with = "crate::core::serialize::sorted_set"))]
+    pub stairs: HashSet<(i32, i32)>, // 楼梯：连接该格与上一层同位置格子的下端坐标
+    pub topology: Topology, // 网格拓扑
+    #[cfg_attr(feature = "serde", serde(with = "crate::core::serialize::sorted_set"))]
+    pub passages: HashSet<((i32, i32), (i32, i32))>, // 相邻单元格之间已打通的墙（六边形 / 极坐标拓扑使用）
+    pub mask: Option<Mask>, // 形状遮罩：被排除的坐标为虚空
+    #[cfg_attr(feature = "serde", serde(with = "crate::core::serialize::sorted_map"))]
+    pub weights: HashMap<(i32, i32), i32>, // 地形权重：进入该格子的代价（未记录的格子为 1）
 }
 
 impl Map {
@@ -32,129 +107,663 @@ impl Map {
         Self::with_size(56, 56, 560, 560)
     }
 
-    /// 创建指定大小的地图
+    /// 创建指定大小的地图，宽高可以不同、可以为奇数；尺寸非法时 panic
     pub fn with_size(m: i32, n: i32, width: i32, height: i32) -> Self {
-        let mut map = Self {
+        Self::try_with_size(m, n, width, height).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// 创建指定大小的地图，尺寸非法时返回错误
+    pub fn try_with_size(m: i32, n: i32, width: i32, height: i32) -> Result<Self, MazeError> {
+        Self::try_with_floors(m, n, 1, width, height)
+    }
+
+    /// 创建多层地图，相邻楼层之间由楼梯连接；起点在最底层，终点在最顶层；尺寸非法时 panic
+    pub fn with_floors(m: i32, n: i32, floors: i32, width: i32, height: i32) -> Self {
+        Self::try_with_floors(m, n, floors, width, height).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// 创建多层地图，尺寸非法时返回错误
+    pub fn try_with_floors(m: i32, n: i32, floors: i32, width: i32, height: i32) -> Result<Self, MazeError> {
+        Self::square(m, n, floors, Topology::Square, width, height)
+    }
+
+    /// 创建环面地图（单层），迷宫通道可以穿过左右、上下边界的接缝；尺寸非法时 panic
+    pub fn torus(m: i32, n: i32, width: i32, height: i32) -> Self {
+        Self::try_torus(m, n, width, height).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// 创建环面地图，尺寸非法（含奇数宽高）时返回错误
+    pub fn try_torus(m: i32, n: i32, width: i32, height: i32) -> Result<Self, MazeError> {
+        Self::square(m, n, 1, Topology::Torus, width, height)
+    }
+
+    /// 创建除外墙外全部为通道、没有内墙的单层方形地图，尺寸非法时返回错误
+    ///
+    /// 搜索前沿比完美迷宫宽得多，用于在大网格上比较 BFS 与并行 BFS。
+    pub fn try_open_grid(m: i32, n: i32, width: i32, height: i32) -> Result<Self, MazeError> {
+        Self::validate_dimensions(m, n, 1, Topology::Square)?;
+        let mut map = Self::blank(m, n, 1, Topology::Square, width, height);
+        for y in 1..n {
+            for x in 1..m {
+                map.grid[(x as usize, y as usize)] = CellState::Path;
+            }
+        }
+        Ok(map)
+    }
+
+    /// 创建方形单元格的地图（Square / Torus）
+    fn square(m: i32, n: i32, floors: i32, topology: Topology, width: i32, height: i32) -> Result<Self, MazeError> {
+        Self::validate_dimensions(m, n, floors, topology)?;
+        let mut map = Self::blank(m, n, floors, topology, width, height);
+        map.generate();
+        Ok(map)
+    }
+
+    /// 检查尺寸能否构成该拓扑的迷宫
+    ///
+    /// 方形网格至少需要 2×2 才能容纳一个单元格；奇数宽高时最后一列 / 行保持为墙。
+    /// 环面的接缝墙位于第 m 列 / 第 n 行，要求宽高为偶数。
+    pub fn validate_dimensions(m: i32, n: i32, floors: i32, topology: Topology) -> Result<(), MazeError> {
+        let invalid = |reason| Err(MazeError::InvalidDimensions { m, n, reason });
+        let min = match topology {
+            Topology::Square | Topology::Torus => 2,
+            Topology::Hex => 1,
+            Topology::Polar => 2,
+        };
+        if m < min || n < min {
+            return invalid("宽高过小，无法容纳单元格");
+        }
+        if floors < 1 {
+            return invalid("楼层数至少为 1");
+        }
+        if topology == Topology::Torus && (m % 2 != 0 || n % 2 != 0 || floors != 1) {
+            return invalid("环面地图要求单层且宽高为偶数");
+        }
+        let rows = n.checked_add(1).and_then(|r| r.checked_mul(floors));
+        let cells = rows.zip(m.checked_add(1)).and_then(|(r, c)| r.checked_mul(c));
+        if cells.is_none() {
+            return invalid("地图过大");
+        }
+        Ok(())
+    }
+
+    /// 以固定种子创建指定拓扑的单层迷宫，相同参数总是得到相同的迷宫
+    ///
+    /// 方形 / 环面的 m、n 为 grid 宽高，六边形为 m × n 个单元格，极坐标为 n 个同心环（忽略 m）。
+    pub fn seeded(topology: Topology, m: i32, n: i32, seed: u64, width: i32, height: i32) -> Result<Self, MazeError> {
+        let mut map = if topology == Topology::Polar {
+            Self::polar_blank(n, width, height)?
+        } else {
+            Self::validate_dimensions(m, n, 1, topology)?;
+            Self::blank(m, n, 1, topology, width, height)
+        };
+        map.generate_with(&mut StdRng::seed_from_u64(seed));
+        Ok(map)
+    }
+
+    /// 创建形状由遮罩决定的单层地图
+    ///
+    /// 遮罩可能分成互不相连的几块（如多个字母），每块各生成一棵生成树；起点/终点取最大一块中
+    /// 第一个/最后一个单元格，保证两者连通。尺寸非法或遮罩内没有单元格时返回错误；极坐标网格不支持遮罩。
+    pub fn with_mask(mask: Mask, topology: Topology, width: i32, height: i32) -> Result<Self, MazeError> {
+        if topology == Topology::Polar {
+            return Err(MazeError::UnsupportedTopology(topology));
+        }
+        Self::validate_dimensions(mask.m, mask.n, 1, topology)?;
+        let (m, n) = (mask.m, mask.n);
+        let mut map = Self::blank(m, n, 1, topology, width, height);
+        map.mask = Some(mask);
+        let cells = map.cell_coords();
+        let (Some(&(sx, sy)), Some(&(ex, ey))) = (cells.first(), cells.last()) else {
+            return Err(MazeError::InvalidDimensions { m, n, reason: "遮罩内没有单元格" });
+        };
+        (map.sx, map.sy, map.ex, map.ey) = (sx, sy, ex, ey);
+        map.generate();
+        let region = map.largest_region();
+        if let (Some(&(sx, sy)), Some(&(ex, ey))) = (region.first(), region.last()) {
+            (map.sx, map.sy, map.ex, map.ey) = (sx, sy, ex, ey);
+        }
+        map.check_endpoints(map.start(), map.end())?;
+        Ok(map)
+    }
+
+    /// 互相连通的单元格最多的一块区域，按 `cell_coords` 的顺序返回其中的单元格
+    fn largest_region(&self) -> Vec<(i32, i32)> {
+        let cells = self.cell_coords();
+        let mut region_of: HashMap<(i32, i32), usize> = HashMap::new();
+        let mut sizes = Vec::new();
+        for &cell in &cells {
+            if region_of.contains_key(&cell) || self.get_cell(cell.0, cell.1) == CellState::Wall {
+                continue;
+            }
+            let id = sizes.len();
+            region_of.insert(cell, id);
+            let mut stack = vec![cell];
+            let mut size = 0;
+            while let Some((x, y)) = stack.pop() {
+                size += 1;
+                for edge in self.neighbors(x, y) {
+                    if let Entry::Vacant(e) = region_of.entry((edge.x, edge.y)) {
+                        e.insert(id);
+                        stack.push((edge.x, edge.y));
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+        let Some(largest) = (0..sizes.len()).max_by_key(|&id| (sizes[id], std::cmp::Reverse(id))) else {
+            return Vec::new();
+        };
+        cells.into_iter().filter(|c| region_of.get(c) == Some(&largest)).collect()
+    }
+
+    /// 创建全部为墙、尚未生成迷宫的地图，起点/终点取默认位置
+    pub(crate) fn blank(m: i32, n: i32, floors: i32, topology: Topology, width: i32, height: i32) -> Self {
+        let floors = floors.max(1);
+        let (ex, ey) = match topology {
+            Topology::Square | Topology::Torus => (last_cell(m), (n + 1) * (floors - 1) + last_cell(n)),
+            Topology::Hex => (m, n),
+            Topology::Polar => (1, n),
+        };
+        Self {
             sx: 1,
             sy: 1,
+            ex,
+            ey,
             m,
             n,
             width,
             height,
-            grid: vec![vec![1; (m + 1) as usize]; (n + 1) as usize],
-        };
-        map.generate();
-        map
+            grid: Grid::new((m + 1) as usize, ((n + 1) * floors) as usize, CellState::Wall),
+            portals: Vec::new(),
+            doors: HashMap::new(),
+            floors,
+            stairs: HashSet::new(),
+            topology,
+            passages: HashSet::new(),
+            mask: None,
+            weights: HashMap::new(),
+        }
     }
 
-    /// Kruskal + 并查集生成完美迷宫（奇数坐标为通道，偶数坐标为墙）
-    fn generate(&mut self) {
-        let mut rng = rand::thread_rng();
+    /// 按拓扑生成迷宫
+    pub(crate) fn generate(&mut self) {
+        self.generate_with(&mut rand::thread_rng());
+    }
+
+    fn generate_with(&mut self, rng: &mut impl Rng) {
+        match self.topology {
+            Topology::Square | Topology::Torus => self.generate_square(rng),
+            Topology::Hex => self.generate_hex(rng),
+            Topology::Polar => self.generate_polar(rng),
+        }
+    }
+
+    /// Kruskal + 并查集生成完美迷宫（层内奇数坐标为通道，偶数坐标为墙）
+    ///
+    /// 多层地图中上下层同位置的单元格之间也是候选边，打通即放置楼梯。
+    /// 楼梯边的随机权重整体偏大，使各层先在层内连通，楼梯数量保持适中。
+    /// 环面地图额外收集跨越接缝的边，接缝处的墙位于第 m 列 / 第 n 行。
+    /// 存在遮罩时只打通两个单元格及中间的墙都位于遮罩内的边。
+    fn generate_square(&mut self, rng: &mut impl Rng) {
 
         // 逻辑单元格数量（仅使用奇数坐标作为可通行单元）
         let cells_x = (self.m / 2) as usize;
         let cells_y = (self.n / 2) as usize;
 
-        // 收集左右/上下相邻单元格之间的墙
-        let mut edges: Vec<((i32, i32), (i32, i32))> = Vec::new();
-        let mut y = 1;
-        while y < self.n {
-            let mut x = 1;
-            while x < self.m {
-                if x + 2 < self.m {
-                    edges.push(((x, y), (x + 2, y)));
-                }
-                if y + 2 < self.n {
-                    edges.push(((x, y), (x, y + 2)));
+        // 收集左右/上下相邻单元格之间的墙以及上下层之间的楼梯，附带随机权重
+        // 元素为 (权重, 单元格 1, 单元格 2, 中间的墙)，楼梯没有中间的墙
+        let wrap = self.topology == Topology::Torus;
+        let mut edges = Vec::new();
+        for f in 0..self.floors {
+            let base = self.floor_offset(f);
+            let mut y = 1;
+            while y < self.n {
+                let mut x = 1;
+                while x < self.m {
+                    if x + 2 < self.m {
+                        edges.push((rng.gen(), (x, base + y), (x + 2, base + y), Some((x + 1, base + y))));
+                    } else if wrap && x > 1 {
+                        edges.push((rng.gen(), (x, base + y), (1, base + y), Some((self.m, base + y))));
+                    }
+                    if y + 2 < self.n {
+                        edges.push((rng.gen(), (x, base + y), (x, base + y + 2), Some((x, base + y + 1))));
+                    } else if wrap && y > 1 {
+                        edges.push((rng.gen(), (x, base + y), (x, base + 1), Some((x, base + self.n))));
+                    }
+                    if f + 1 < self.floors {
+                        let key = rng.gen_range(u32::MAX / 2..=u32::MAX);
+                        edges.push((key, (x, base + y), (x, base + y + self.n + 1), None));
+                    }
+                    x += 2;
                 }
-                x += 2;
+                y += 2;
             }
-            y += 2;
         }
-        edges.shuffle(&mut rng);
+        edges.sort_unstable_by_key(|e| e.0);
 
-        let mut uf = UnionFind::new(cells_x * cells_y);
+        let mut uf = UnionFind::new(cells_x * cells_y * self.floors as usize);
 
         // 初始化全部为墙，保证起点/终点可通行
-        self.grid[self.sy as usize][self.sx as usize] = 0;
-        self.grid[(self.n - 1) as usize][(self.m - 1) as usize] = 0;
+        let (sx, sy) = self.start();
+        let (ex, ey) = self.end();
+        self.set_cell(sx, sy, CellState::Path);
+        self.set_cell(ex, ey, CellState::Path);
 
         // Kruskal 逐墙打通
-        for ((x1, y1), (x2, y2)) in edges {
-            let i1 = Self::cell_idx(x1, y1, cells_x);
-            let i2 = Self::cell_idx(x2, y2, cells_x);
+        for (_, (x1, y1), (x2, y2), wall) in edges {
+            let wall_exists = match wall {
+                Some((wx, wy)) => self.in_bounds(wx, wy),
+                None => true,
+            };
+            if !self.in_bounds(x1, y1) || !self.in_bounds(x2, y2) || !wall_exists {
+                continue;
+            }
+            let i1 = self.cell_idx(x1, y1, cells_x, cells_y);
+            let i2 = self.cell_idx(x2, y2, cells_x, cells_y);
             if uf.find(i1) != uf.find(i2) {
                 uf.union(i1, i2);
 
-                // 打通两个单元格及其中间的墙
-                let wx = (x1 + x2) / 2;
-                let wy = (y1 + y2) / 2;
-                self.grid[y1 as usize][x1 as usize] = 0;
-                self.grid[y2 as usize][x2 as usize] = 0;
-                self.grid[wy as usize][wx as usize] = 0;
+                self.grid[(x1 as usize, y1 as usize)] = CellState::Path;
+                self.grid[(x2 as usize, y2 as usize)] = CellState::Path;
+                match wall {
+                    // 打通两个单元格中间的墙
+                    Some((wx, wy)) => self.grid[(wx as usize, wy as usize)] = CellState::Path,
+                    // 跨层：放置楼梯
+                    None => {
+                        self.stairs.insert((x1, y1));
+                    }
+                }
             }
         }
     }
 
     #[inline]
-    fn cell_idx(x: i32, y: i32, cells_x: usize) -> usize {
-        (y as usize / 2) * cells_x + (x as usize / 2)
+    fn cell_idx(&self, x: i32, y: i32, cells_x: usize, cells_y: usize) -> usize {
+        let local_y = (y - self.floor_offset(self.floor_of(y))) as usize;
+        self.floor_of(y) as usize * cells_x * cells_y + (local_y / 2) * cells_x + (x as usize / 2)
+    }
+
+    /// 获取 y 所在楼层
+    #[inline]
+    pub fn floor_of(&self, y: i32) -> i32 {
+        y / (self.n + 1)
+    }
+
+    /// 获取楼层 floor 第 0 行在 grid 中的行号
+    #[inline]
+    pub fn floor_offset(&self, floor: i32) -> i32 {
+        floor * (self.n + 1)
     }
     
-    /// 检查坐标是否在地图范围内
+    /// 检查坐标是否在地图范围内（被遮罩排除的虚空、极坐标环内不存在的序号不在范围内）
     pub fn in_bounds(&self, x: i32, y: i32) -> bool {
-        x >= 1 && x <= self.m && y >= 1 && y <= self.n
+        let local_y = y - self.floor_offset(self.floor_of(y));
+        x >= 1 && x <= self.m && y >= 1 && self.floor_of(y) < self.floors && local_y >= 1
+            && (self.topology != Topology::Polar || x <= ring_size(y - 1))
+            && !self.is_void(x, y)
+    }
+
+    /// 检查坐标是否为被遮罩排除的虚空
+    pub fn is_void(&self, x: i32, y: i32) -> bool {
+        self.mask.as_ref().is_some_and(|mask| !mask.contains(x, y))
     }
     
     /// 检查坐标是否可通行
     pub fn is_passable(&self, x: i32, y: i32) -> bool {
-        self.in_bounds(x, y) && self.grid[y as usize][x as usize] == 0
+        self.in_bounds(x, y) && self.grid[(x as usize, y as usize)] == CellState::Path
     }
     
     /// 获取单元格状态
-    pub fn get_cell(&self, x: i32, y: i32) -> i32 {
+    pub fn get_cell(&self, x: i32, y: i32) -> CellState {
         if self.in_bounds(x, y) {
-            self.grid[y as usize][x as usize]
+            self.grid[(x as usize, y as usize)]
         } else {
-            1 // 越界视为墙
+            CellState::Wall // 越界视为墙
         }
     }
     
-    /// 设置单元格状态
-    pub fn set_cell(&mut self, x: i32, y: i32, value: i32) {
+    /// 设置单元格状态（越界时忽略，需要区分时使用 `try_set_cell`）
+    pub fn set_cell(&mut self, x: i32, y: i32, state: CellState) {
         if self.in_bounds(x, y) {
-            self.grid[y as usize][x as usize] = value;
+            self.grid[(x as usize, y as usize)] = state;
+        }
+    }
+
+    /// 设置单元格状态，越界时返回错误
+    pub fn try_set_cell(&mut self, x: i32, y: i32, state: CellState) -> Result<(), MazeError> {
+        if !self.in_bounds(x, y) {
+            return Err(MazeError::OutOfBounds { x, y });
+        }
+        self.grid[(x as usize, y as usize)] = state;
+        Ok(())
+    }
+
+    /// 检查搜索的起点、终点：越界或为墙时返回错误
+    pub fn check_endpoints(&self, (sx, sy): (i32, i32), (ex, ey): (i32, i32)) -> Result<(), MazeError> {
+        if let Some((x, y)) = [(sx, sy), (ex, ey)].into_iter().find(|&(x, y)| !self.in_bounds(x, y)) {
+            return Err(MazeError::OutOfBounds { x, y });
+        }
+        if self.get_cell(sx, sy) == CellState::Wall {
+            return Err(MazeError::StartBlocked { x: sx, y: sy });
         }
+        if self.get_cell(ex, ey) == CellState::Wall {
+            return Err(MazeError::GoalBlocked { x: ex, y: ey });
+        }
+        Ok(())
+    }
+
+    /// 将搜索轨迹中的一步应用到地图：按步骤颜色设置格子状态，其它颜色不改变地图
+    pub fn apply_step(&mut self, step: &Point) {
+        let value = match step.color {
+            Color::YELLOW => CellState::Start,
+            Color::SKY_BLUE => CellState::Visited,
+            Color::LIGHT_RED => CellState::Backtrack,
+            Color::AQUAMARINE => CellState::Solution,
+            _ => return,
+        };
+        self.set_cell(step.x, step.y, value);
+    }
+
+    /// 从搜索轨迹中提取最终路径（从起点到终点）及每个格子离开时的方向
+    ///
+    /// 最终路径由回溯（LIGHT_RED，终点到起点的顺序）或最终路径（AQUAMARINE）颜色的步骤组成。
+    pub fn trace_path(&self, steps: &[Point]) -> Vec<(i32, i32, Direction)> {
+        let mut path_steps: Vec<&Point> = steps.iter()
+            .filter(|s| s.color == Color::LIGHT_RED || s.color == Color::AQUAMARINE)
+            .collect();
+        if path_steps.first().is_some_and(|s| s.color == Color::LIGHT_RED) {
+            path_steps.reverse();
+        }
+
+        // 去重（可能有重复的点）
+        let mut seen = HashSet::new();
+        path_steps.retain(|s| seen.insert((s.x, s.y)));
+
+        path_steps.iter()
+            .enumerate()
+            .map(|(i, current)| {
+                let direction = path_steps.get(i + 1).map_or(Direction::None, |next| {
+                    self.neighbors(current.x, current.y)
+                        .into_iter()
+                        .find(|e| (e.x, e.y) == (next.x, next.y))
+                        .map_or(Direction::None, |e| e.direction)
+                });
+                (current.x, current.y, direction)
+            })
+            .collect()
     }
     
+    /// 获取从 (x, y) 出发的所有邻接边（不含墙，包含传送门，遵守单向门）
+    pub fn neighbors(&self, x: i32, y: i32) -> Vec<Edge> {
+        let mut edges = Vec::new();
+        self.neighbors_into(x, y, &mut edges);
+        edges
+    }
+
+    /// 与 `neighbors` 相同，但写入调用方提供的缓冲区（先清空），扩展大量节点时可复用同一块内存
+    pub fn neighbors_into(&self, x: i32, y: i32, edges: &mut Vec<Edge>) {
+        edges.clear();
+        self.undirected_neighbors(x, y, edges);
+        edges.retain(|e| self.can_move(x, y, e));
+    }
+
+    /// 获取可以一步到达 (x, y) 的所有前驱（边的方向为从前驱指向 (x, y)），用于反向搜索
+    pub fn predecessors(&self, x: i32, y: i32) -> Vec<Edge> {
+        let mut edges = Vec::new();
+        self.undirected_neighbors(x, y, &mut edges);
+        edges
+            .into_iter()
+            // 反向边的代价为进入 (x, y) 的地形代价，而不是前驱格子的代价
+            .map(|e| {
+                let cost = if e.direction == Direction::Portal { e.cost } else { self.cell_weight(x, y) };
+                Edge { direction: e.direction.reverse(), cost, ..e }
+            })
+            .filter(|e| self.can_move(e.x, e.y, &Edge { x, y, ..*e }))
+            .collect()
+    }
+
+    /// 不考虑单向门时的邻接边，追加到 edges 末尾
+    fn undirected_neighbors(&self, x: i32, y: i32, edges: &mut Vec<Edge>) {
+        let first = edges.len();
+        match self.topology {
+            Topology::Square | Topology::Torus => self.square_neighbors(x, y, edges),
+            Topology::Hex => self.hex_neighbors(x, y, edges),
+            Topology::Polar => self.polar_neighbors(x, y, edges),
+        }
+        for edge in edges[first..].iter_mut() {
+            edge.cost = self.cell_weight(edge.x, edge.y);
+        }
+        for portal in &self.portals {
+            if let Some((px, py)) = portal.partner(x, y) {
+                edges.push(Edge { x: px, y: py, direction: Direction::Portal, cost: portal.cost });
+            }
+        }
+    }
+
+    /// 方形网格的 4 邻域及楼梯（环面地图越过边界时取模回绕）
+    fn square_neighbors(&self, x: i32, y: i32, edges: &mut Vec<Edge>) {
+        for (dx, dy, dir) in DIRECTIONS.iter() {
+            let (nx, ny) = self.wrap(x + dx, y + dy);
+            if self.in_bounds(nx, ny) && self.get_cell(nx, ny) != CellState::Wall {
+                edges.push(Edge { x: nx, y: ny, direction: *dir, cost: 1 });
+            }
+        }
+        if self.stairs.contains(&(x, y)) {
+            edges.push(Edge { x, y: y + self.n + 1, direction: Direction::Ascend, cost: 1 });
+        }
+        if self.stairs.contains(&(x, y - self.n - 1)) {
+            edges.push(Edge { x, y: y - self.n - 1, direction: Direction::Descend, cost: 1 });
+        }
+    }
+
+    /// 环面地图（单层）中将越界坐标回绕到 1..=m / 1..=n，其他拓扑原样返回
+    pub fn wrap(&self, x: i32, y: i32) -> (i32, i32) {
+        if self.topology != Topology::Torus {
+            return (x, y);
+        }
+        ((x - 1).rem_euclid(self.m) + 1, (y - 1).rem_euclid(self.n) + 1)
+    }
+
+    /// 两个相邻单元格之间的墙是否已打通（六边形 / 极坐标拓扑）
+    pub fn has_passage(&self, a: (i32, i32), b: (i32, i32)) -> bool {
+        self.passages.contains(&(a.min(b), a.max(b)))
+    }
+
+    /// 打通两个相邻单元格之间的墙（六边形 / 极坐标拓扑）
+    pub fn open_passage(&mut self, a: (i32, i32), b: (i32, i32)) {
+        self.passages.insert((a.min(b), a.max(b)));
+    }
+
+    /// 进入 (x, y) 的地形代价
+    pub fn cell_weight(&self, x: i32, y: i32) -> i32 {
+        self.weights.get(&(x, y)).copied().unwrap_or(1)
+    }
+
+    /// 设置进入 (x, y) 的地形代价；代价小于 1 时按 1 处理，保证 A* 启发函数可采纳
+    pub fn set_weight(&mut self, x: i32, y: i32, weight: i32) {
+        if !self.in_bounds(x, y) {
+            return;
+        }
+        if weight > 1 {
+            self.weights.insert((x, y), weight);
+        } else {
+            self.weights.remove(&(x, y));
+        }
+    }
+
+    /// 单向门只允许沿其方向进入和离开
+    fn can_move(&self, x: i32, y: i32, edge: &Edge) -> bool {
+        let allows = |cx: i32, cy: i32| match self.doors.get(&(cx, cy)) {
+            Some(d) => *d == edge.direction,
+            None => true,
+        };
+        allows(x, y) && allows(edge.x, edge.y)
+    }
+
+    /// 获取从 (x1, y1) 直接移动到 (x2, y2) 的最小代价，不相邻时返回 None
+    pub fn edge_cost(&self, x1: i32, y1: i32, x2: i32, y2: i32) -> Option<i32> {
+        self.neighbors(x1, y1)
+            .into_iter()
+            .filter(|e| e.x == x2 && e.y == y2)
+            .map(|e| e.cost)
+            .min()
+    }
+
+    /// 估计两点之间的最短路径代价下界（不考虑传送门；方形网格为层内曼哈顿距离加楼层差，
+    /// 环面取绕接缝后的较短距离，六边形网格为六边形距离，极坐标网格为环号差）
+    pub fn estimate(&self, x1: i32, y1: i32, x2: i32, y2: i32) -> i32 {
+        match self.topology {
+            Topology::Hex => return hex_distance(x1, y1, x2, y2),
+            // 每一步至多跨越一个环
+            Topology::Polar => return (y1 - y2).abs(),
+            Topology::Square | Topology::Torus => {}
+        }
+        let (f1, f2) = (self.floor_of(y1), self.floor_of(y2));
+        let local_y1 = y1 - self.floor_offset(f1);
+        let local_y2 = y2 - self.floor_offset(f2);
+        let mut dx = (x1 - x2).abs();
+        let mut dy = (local_y1 - local_y2).abs();
+        if self.topology == Topology::Torus {
+            // 环面上可以反向绕过接缝
+            dx = dx.min(self.m - dx);
+            dy = dy.min(self.n - dy);
+        }
+        dx + dy + (f1 - f2).abs()
+    }
+
+    /// 添加一对传送门，两端会被打通为通道；代价小于 0 时按 0 处理
+    pub fn add_portal(&mut self, a: (i32, i32), b: (i32, i32), cost: i32) {
+        if !self.in_bounds(a.0, a.1) || !self.in_bounds(b.0, b.1) || a == b {
+            return;
+        }
+        self.grid[(a.0 as usize, a.1 as usize)] = CellState::Path;
+        self.grid[(b.0 as usize, b.1 as usize)] = CellState::Path;
+        self.portals.push(Portal { a, b, cost: cost.max(0) });
+    }
+
+    /// 在随机通道单元格之间放置若干对传送门（不占用起点、终点及已有传送门）
+    pub fn add_random_portals(&mut self, count: usize, cost: i32) {
+        let mut rng = rand::thread_rng();
+        let start = self.start();
+        let end = self.end();
+        let mut cells: Vec<(i32, i32)> = Vec::new();
+        for (x, y) in self.cell_coords() {
+            let used = self.portals.iter().any(|p| p.a == (x, y) || p.b == (x, y));
+            if self.is_passable(x, y) && (x, y) != start && (x, y) != end && !used {
+                cells.push((x, y));
+            }
+        }
+        cells.shuffle(&mut rng);
+        for pair in cells.chunks_exact(2).take(count) {
+            self.add_portal(pair[0], pair[1], cost);
+        }
+    }
+
+    /// 将 (x, y) 设为只能沿 direction 穿过的单向门
+    pub fn add_door(&mut self, x: i32, y: i32, direction: Direction) {
+        if self.in_bounds(x, y) {
+            self.grid[(x as usize, y as usize)] = CellState::Path;
+            self.doors.insert((x, y), direction);
+        }
+    }
+
+    /// 在随机的通道连接处（两个单元格之间被打通的墙）放置若干单向门
+    pub fn add_random_doors(&mut self, count: usize) {
+        let mut rng = rand::thread_rng();
+        if self.topology.uses_passages() {
+            // 六边形 / 极坐标拓扑：任选单元格，沿其某条已打通的边放置
+            let mut cells = self.cell_coords();
+            cells.retain(|c| !self.doors.contains_key(c) && *c != self.start() && *c != self.end());
+            cells.shuffle(&mut rng);
+            for (x, y) in cells.into_iter().take(count) {
+                if let Some(edge) = self.neighbors(x, y).choose(&mut rng) {
+                    let direction = edge.direction;
+                    self.add_door(x, y, direction);
+                }
+            }
+            return;
+        }
+        let mut cells: Vec<(i32, i32)> = Vec::new();
+        for f in 0..self.floors {
+            let base = self.floor_offset(f);
+            for local_y in 1..self.n {
+                for x in 1..self.m {
+                    // 层内恰有一个坐标为偶数的可通行格子即为连接处
+                    let y = base + local_y;
+                    if (x % 2 == 0) != (local_y % 2 == 0) && self.is_passable(x, y) && !self.doors.contains_key(&(x, y)) {
+                        cells.push((x, y));
+                    }
+                }
+            }
+        }
+        cells.shuffle(&mut rng);
+        for (x, y) in cells.into_iter().take(count) {
+            let forward = rng.gen_bool(0.5);
+            let direction = match (x % 2 == 0, forward) {
+                (true, true) => Direction::Right,
+                (true, false) => Direction::Left,
+                (false, true) => Direction::Down,
+                (false, false) => Direction::Up,
+            };
+            self.add_door(x, y, direction);
+        }
+    }
+
     /// 获取起点坐标
     pub fn start(&self) -> (i32, i32) {
         (self.sx, self.sy)
     }
     
-    /// 获取终点坐标
+    /// 获取终点坐标（多层地图位于最顶层）
     pub fn end(&self) -> (i32, i32) {
-        (self.m - 1, self.n - 1)
+        (self.ex, self.ey)
+    }
+
+    /// 所有可作为单元格的坐标（方形网格为各层中层内坐标均为奇数的格子，不含虚空）
+    pub fn cell_coords(&self) -> Vec<(i32, i32)> {
+        let mut cells = Vec::new();
+        if self.topology.uses_passages() {
+            for y in 1..=self.n {
+                for x in 1..=self.m {
+                    cells.push((x, y));
+                }
+            }
+        } else {
+            for f in 0..self.floors {
+                let base = self.floor_offset(f);
+                for local_y in (1..self.n).step_by(2) {
+                    for x in (1..self.m).step_by(2) {
+                        cells.push((x, base + local_y));
+                    }
+                }
+            }
+        }
+        cells.retain(|&(x, y)| self.in_bounds(x, y));
+        cells
     }
     
+    /// 获取 grid 坐标 (x, y) 的显示颜色（与 GUI 的 CELL_COLORS 一致，虚空为浅灰）
+    pub fn cell_color(&self, x: i32, y: i32) -> Color {
+        match self.grid[(x as usize, y as usize)] {
+            _ if self.is_void(x, y) => Color::VOID,
+            CellState::Path => Color::WHITE,
+            CellState::Wall => Color::BLACK,
+            CellState::Visited => Color::SKY_BLUE,
+            CellState::Backtrack => Color::LIGHT_RED,
+            CellState::Start => Color::YELLOW,
+            CellState::Solution => Color::AQUAMARINE,
+        }
+    }
+
     /// 渲染地图到像素缓冲区
-    pub fn render(&self, buffer: &mut Vec<u32>, buf_width: usize, buf_height: usize) {
+    pub fn render(&self, buffer: &mut [u32], buf_width: usize, buf_height: usize) {
         let cell_w = buf_width / (self.m + 1) as usize;
-        let cell_h = buf_height / (self.n + 1) as usize;
+        let cell_h = buf_height / self.grid.height();
         
-        for ny in 0..=self.n as usize {
+        for ny in 0..self.grid.height() {
             for nx in 0..=self.m as usize {
-                let color = match self.grid[ny][nx] {
-                    0 => Color::WHITE,
-                    1 => Color::BLACK,
-                    2 => Color::SKY_BLUE,
-                    3 => Color::LIGHT_RED,
-                    4 => Color::YELLOW,
-                    5 => Color::AQUAMARINE,
-                    _ => Color::WHITE,
-                };
-                
-                let color_u32 = color.to_u32();
+                let color_u32 = self.cell_color(nx as i32, ny as i32).to_u32();
                 
                 for py in (ny * cell_h)..((ny + 1) * cell_h).min(buf_height) {
                     for px in (nx * cell_w)..((nx + 1) * cell_w).min(buf_width) {
@@ -173,27 +782,27 @@ impl Default for Map {
 }
 
 // 简单的并查集实现（路径压缩 + 按大小合并）
-struct UnionFind {
+pub(crate) struct UnionFind {
     parent: Vec<usize>,
     size: Vec<usize>,
 }
 
 impl UnionFind {
-    fn new(n: usize) -> Self {
+    pub(crate) fn new(n: usize) -> Self {
         Self {
             parent: (0..n).collect(),
             size: vec![1; n],
         }
     }
 
-    fn find(&mut self, x: usize) -> usize {
+    pub(crate) fn find(&mut self, x: usize) -> usize {
         if self.parent[x] != x {
             self.parent[x] = self.find(self.parent[x]);
         }
         self.parent[x]
     }
 
-    fn union(&mut self, a: usize, b: usize) {
+    pub(crate) fn union(&mut self, a: usize, b: usize) {
         let mut pa = self.find(a);
         let mut pb = self.find(b);
         if pa == pb {
@@ -228,4 +837,109 @@ mod tests {
         assert!(!map.in_bounds(0, 0));
         assert!(!map.in_bounds(11, 11));
     }
+
+    #[test]
+    fn test_try_set_cell_and_endpoints() {
+        let mut map = Map::with_size(10, 10, 100, 100);
+        assert_eq!(map.try_set_cell(0, 3, CellState::Path), Err(MazeError::OutOfBounds { x: 0, y: 3 }));
+        assert_eq!(map.try_set_cell(3, 3, CellState::Visited), Ok(()));
+        assert_eq!(map.get_cell(3, 3), CellState::Visited);
+
+        let (start, goal) = (map.start(), map.end());
+        assert_eq!(map.check_endpoints(start, goal), Ok(()));
+        assert_eq!(map.check_endpoints(start, (11, 1)), Err(MazeError::OutOfBounds { x: 11, y: 1 }));
+        map.set_cell(start.0, start.1, CellState::Wall);
+        assert_eq!(map.check_endpoints(start, goal), Err(MazeError::StartBlocked { x: start.0, y: start.1 }));
+        assert_eq!(map.check_endpoints(goal, start), Err(MazeError::GoalBlocked { x: start.0, y: start.1 }));
+    }
+
+    #[test]
+    fn test_odd_and_rectangular_sizes() {
+        for (m, n) in [(11, 7), (10, 15), (2, 2), (3, 3), (40, 6)] {
+            let map = Map::with_size(m, n, 100, 100);
+            let (ex, ey) = map.end();
+            assert_eq!((ex % 2, ey % 2), (1, 1));
+            assert!(map.is_passable(ex, ey));
+            let result = crate::bfs_search(&mut map.clone(), 1, 1, ex, ey);
+            assert!(result.found, "{}×{}", m, n);
+        }
+    }
+
+    #[test]
+    fn test_invalid_dimensions() {
+        assert!(matches!(
+            Map::try_with_size(1, 10, 100, 100),
+            Err(MazeError::InvalidDimensions { m: 1, n: 10, .. })
+        ));
+        assert!(Map::try_with_floors(10, 10, 0, 100, 100).is_err());
+        assert!(Map::try_torus(11, 10, 100, 100).is_err());
+        assert!(Map::try_torus(12, 10, 100, 100).is_ok());
+        // 宽高加一时溢出也应返回错误而不是 panic
+        for (m, n) in [(i32::MAX, 10), (10, i32::MAX), (i32::MAX, i32::MAX)] {
+            assert!(matches!(Map::try_with_size(m, n, 100, 100), Err(MazeError::InvalidDimensions { .. })));
+            assert!(Map::try_hex(m, n, 100, 100).is_err());
+        }
+    }
+
+    #[test]
+    fn test_multi_floor_map() {
+        let map = Map::with_floors(10, 10, 3, 100, 100);
+        assert_eq!(map.grid.height(), 33);
+        assert_eq!(map.end(), (9, 31));
+        assert!(!map.in_bounds(1, 11));
+        assert!(map.in_bounds(1, 12));
+        // 完美迷宫：各层之间恰好连通，至少需要 floors - 1 个楼梯
+        assert!(map.stairs.len() >= 2);
+        let &(x, y) = map.stairs.iter().next().unwrap();
+        assert_eq!(map.edge_cost(x, y, x, y + 11), Some(1));
+        assert_eq!(map.estimate(1, 1, 9, 31), 8 + 8 + 2);
+    }
+
+    #[test]
+    fn test_torus_wraps() {
+        let map = Map::torus(10, 10, 100, 100);
+        assert_eq!(map.wrap(0, 3), (10, 3));
+        assert_eq!(map.wrap(11, 3), (1, 3));
+        assert_eq!(map.wrap(3, 0), (3, 10));
+        assert_eq!(map.estimate(1, 1, 9, 9), 4);
+        // 接缝处打通的墙使两侧单元格相邻
+        for y in (1..10).step_by(2) {
+            if map.is_passable(10, y) {
+                assert_eq!(map.edge_cost(1, y, 10, y), Some(1));
+                assert_eq!(map.edge_cost(10, y, 1, y), Some(1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_portal_neighbors() {
+        let mut map = Map::with_size(10, 10, 100, 100);
+        map.add_portal((1, 1), (9, 9), 3);
+        let edges = map.neighbors(1, 1);
+        assert!(edges.contains(&Edge { x: 9, y: 9, direction: Direction::Portal, cost: 3 }));
+        assert_eq!(map.edge_cost(9, 9, 1, 1), Some(3));
+    }
+
+    #[test]
+    fn test_one_way_door() {
+        let mut map = Map::with_size(10, 10, 100, 100);
+        map.add_door(2, 1, Direction::Right);
+        assert_eq!(map.edge_cost(1, 1, 2, 1), Some(1));
+        assert_eq!(map.edge_cost(2, 1, 1, 1), None);
+        assert!(map.predecessors(2, 1).iter().all(|e| (e.x, e.y) == (1, 1)));
+    }
+
+    #[test]
+    fn test_seeded_maps_are_reproducible() {
+        for topology in [Topology::Square, Topology::Torus, Topology::Hex, Topology::Polar] {
+            let a = Map::seeded(topology, 20, 14, 7, 200, 140).unwrap();
+            let b = Map::seeded(topology, 20, 14, 7, 200, 140).unwrap();
+            assert_eq!(a.grid, b.grid);
+            assert_eq!(a.passages, b.passages);
+        }
+        let a = Map::seeded(Topology::Square, 20, 14, 7, 200, 140).unwrap();
+        let c = Map::seeded(Topology::Square, 20, 14, 8, 200, 140).unwrap();
+        assert_ne!(a.grid, c.grid);
+        assert!(Map::seeded(Topology::Torus, 7, 8, 1, 70, 80).is_err());
+    }
 }
@@ -0,0 +1,161 @@
+//! 形状遮罩模块 - 描述不规则迷宫中哪些坐标存在
+//!
+//! 遮罩与地图的 grid 坐标一一对应（1..=m × 1..=n），被遮罩排除的坐标视为虚空：
+//! 生成器不会在其中打通通道，搜索也不会进入。
+
+/// 形状遮罩
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawMask"))]
+pub struct Mask {
+    pub m: i32,        // 宽度
+    pub n: i32,        // 高度
+    cells: Vec<bool>,  // 行优先存储，下标 (y - 1) * m + (x - 1)
+}
+
+/// `Mask` 的反序列化中间结构，转换时检查 cells 的长度
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawMask {
+    m: i32,
+    n: i32,
+    cells: Vec<bool>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawMask> for Mask {
+    type Error = crate::core::error::MazeError;
+
+    fn try_from(RawMask { m, n, cells }: RawMask) -> Result<Self, Self::Error> {
+        if m < 0 || n < 0 || cells.len() as i64 != m as i64 * n as i64 {
+            return Err(crate::core::error::MazeError::InvalidDimensions { m, n, reason: "遮罩数据长度与尺寸不符" });
+        }
+        Ok(Self { m, n, cells })
+    }
+}
+
+impl Mask {
+    /// 创建全部存在的矩形遮罩
+    pub fn new(m: i32, n: i32) -> Self {
+        Self { m, n, cells: vec![true; (m.max(0) * n.max(0)) as usize] }
+    }
+
+    /// 按谓词创建遮罩，f(x, y) 为 true 的坐标存在
+    pub fn from_fn(m: i32, n: i32, f: impl Fn(i32, i32) -> bool) -> Self {
+        let mut mask = Self::new(m, n);
+        for y in 1..=n {
+            for x in 1..=m {
+                mask.set(x, y, f(x, y));
+            }
+        }
+        mask
+    }
+
+    /// 内切于 m × n 矩形的圆（椭圆）
+    pub fn circle(m: i32, n: i32) -> Self {
+        let (cx, cy) = ((m + 1) as f32 / 2.0, (n + 1) as f32 / 2.0);
+        let (rx, ry) = (m as f32 / 2.0, n as f32 / 2.0);
+        Self::from_fn(m, n, |x, y| {
+            let dx = (x as f32 - cx) / rx;
+            let dy = (y as f32 - cy) / ry;
+            dx * dx + dy * dy <= 1.0
+        })
+    }
+
+    /// 内切于 m × n 矩形的菱形
+    pub fn diamond(m: i32, n: i32) -> Self {
+        let (cx, cy) = ((m + 1) as f32 / 2.0, (n + 1) as f32 / 2.0);
+        let (rx, ry) = (m as f32 / 2.0, n as f32 / 2.0);
+        Self::from_fn(m, n, |x, y| {
+            (x as f32 - cx).abs() / rx + (y as f32 - cy).abs() / ry <= 1.0
+        })
+    }
+
+    /// 从文本位图加载遮罩：'#'、'1'、'X' 表示存在，其余字符为虚空
+    ///
+    /// 每个字符放大为 scale × scale 个坐标，便于用少量字符描绘字母等形状。
+    pub fn from_text(text: &str, scale: i32) -> Self {
+        let scale = scale.max(1);
+        let rows: Vec<Vec<bool>> = text
+            .lines()
+            .map(|line| line.chars().map(|c| matches!(c, '#' | '1' | 'X')).collect())
+            .collect();
+        let cols = rows.iter().map(|r| r.len()).max().unwrap_or(0) as i32;
+        Self::from_fn(cols * scale, rows.len() as i32 * scale, |x, y| {
+            let row = &rows[((y - 1) / scale) as usize];
+            row.get(((x - 1) / scale) as usize).copied().unwrap_or(false)
+        })
+    }
+
+    /// 坐标是否存在（越界视为不存在）
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= 1 && x <= self.m && y >= 1 && y <= self.n && self.cells[((y - 1) * self.m + (x - 1)) as usize]
+    }
+
+    /// 设置坐标是否存在（越界忽略）
+    pub fn set(&mut self, x: i32, y: i32, present: bool) {
+        if x >= 1 && x <= self.m && y >= 1 && y <= self.n {
+            self.cells[((y - 1) * self.m + (x - 1)) as usize] = present;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::error::MazeError;
+    use crate::map::{Map, Topology};
+
+    #[test]
+    fn test_mask_from_text() {
+        let mask = Mask::from_text("#.\n##", 2);
+        assert_eq!((mask.m, mask.n), (4, 4));
+        assert!(mask.contains(2, 2));
+        assert!(!mask.contains(3, 1));
+        assert!(mask.contains(4, 4));
+    }
+
+    #[test]
+    fn test_masked_maze_stays_inside() {
+        let mask = Mask::circle(21, 21);
+        let map = Map::with_mask(mask.clone(), Topology::Square, 210, 210).unwrap();
+        for y in 1..=21 {
+            for x in 1..=21 {
+                if !mask.contains(x, y) {
+                    assert!(!map.is_passable(x, y));
+                    assert!(map.is_void(x, y));
+                }
+            }
+        }
+        let (sx, sy) = map.start();
+        let (ex, ey) = map.end();
+        assert!(mask.contains(sx, sy) && mask.contains(ex, ey));
+        let result = crate::bfs_search(&mut map.clone(), sx, sy, ex, ey);
+        assert!(result.found);
+    }
+
+    #[test]
+    fn test_disconnected_mask_uses_connected_endpoints() {
+        // 左右两块互不相连，右边一块更大
+        let mask = Mask::from_text("##..###\n##..###", 3);
+        let map = Map::with_mask(mask, Topology::Square, 210, 60).unwrap();
+        let ((sx, sy), (ex, ey)) = (map.start(), map.end());
+        assert!(sx > 12 && ex > 12, "{:?} {:?}", (sx, sy), (ex, ey));
+        for algorithm in crate::Algorithm::ALL {
+            let result = crate::solve_between(&mut map.clone(), algorithm, (sx, sy), (ex, ey));
+            assert!(result.found, "{:?}", algorithm);
+        }
+    }
+
+    #[test]
+    fn test_with_mask_rejects_invalid_masks() {
+        let empty = Mask::from_fn(9, 9, |_, _| false);
+        assert!(matches!(Map::with_mask(empty, Topology::Square, 90, 90), Err(MazeError::InvalidDimensions { .. })));
+        assert!(matches!(Map::with_mask(Mask::new(1, 1), Topology::Square, 10, 10), Err(MazeError::InvalidDimensions { .. })));
+        assert!(matches!(Map::with_mask(Mask::new(9, 9), Topology::Torus, 90, 90), Err(MazeError::InvalidDimensions { .. })));
+        assert!(matches!(Map::with_mask(Mask::new(9, 9), Topology::Polar, 90, 90), Err(MazeError::UnsupportedTopology(_))));
+        // 只剩偶数坐标时没有方形单元格
+        let lattice = Mask::from_fn(9, 9, |x, y| x % 2 == 0 || y % 2 == 0);
+        assert!(matches!(Map::with_mask(lattice, Topology::Square, 90, 90), Err(MazeError::InvalidDimensions { .. })));
+    }
+}
@@ -1,5 +1,18 @@
 pub mod map;
+pub mod grid;
 pub mod point;
+pub mod hex;
+pub mod mask;
+pub mod polar;
+pub mod error;
+pub mod text;
+pub mod raster;
+#[cfg(feature = "serde")]
+pub(crate) mod serialize;
 
-pub use map::Map;
+pub use map::{Map, Edge, Portal, Topology};
+pub use grid::{CellState, Grid};
+pub use mask::Mask;
+pub use error::MazeError;
+pub use raster::ImageImport;
 pub use point::{Point, Direction, Color};
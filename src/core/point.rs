@@ -2,12 +2,20 @@
 
 /// 方向枚举
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     None,
     Up,
     Down,
     Left,
     Right,
+    Portal,  // 经传送门跳转
+    Ascend,  // 经楼梯上楼
+    Descend, // 经楼梯下楼
+    UpLeft,    // 六边形网格：左上
+    UpRight,   // 六边形网格：右上
+    DownLeft,  // 六边形网格：左下
+    DownRight, // 六边形网格：右下
 }
 
 impl Direction {
@@ -19,6 +27,13 @@ impl Direction {
             Direction::Down => '↓',
             Direction::Left => '←',
             Direction::Right => '→',
+            Direction::Portal => '◎',
+            Direction::Ascend => '▲',
+            Direction::Descend => '▼',
+            Direction::UpLeft => '↖',
+            Direction::UpRight => '↗',
+            Direction::DownLeft => '↙',
+            Direction::DownRight => '↘',
         }
     }
 
@@ -30,12 +45,20 @@ impl Direction {
             Direction::Down => Direction::Up,
             Direction::Left => Direction::Right,
             Direction::Right => Direction::Left,
+            Direction::Portal => Direction::Portal,
+            Direction::Ascend => Direction::Descend,
+            Direction::Descend => Direction::Ascend,
+            Direction::UpLeft => Direction::DownRight,
+            Direction::UpRight => Direction::DownLeft,
+            Direction::DownLeft => Direction::UpRight,
+            Direction::DownRight => Direction::UpLeft,
         }
     }
 }
 
 /// 颜色结构体 (RGB)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -52,6 +75,11 @@ impl Color {
         ((self.r as u32) << 16) | ((self.g as u32) << 8) | (self.b as u32)
     }
 
+    /// 转换为 `#rrggbb` 格式 (用于 SVG)
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
     // 预定义颜色
     pub const WHITE: Color = Color::new(255, 255, 255);
     pub const BLACK: Color = Color::new(0, 10, 0);
@@ -59,10 +87,12 @@ impl Color {
     pub const SKY_BLUE: Color = Color::new(135, 206, 235);
     pub const LIGHT_RED: Color = Color::new(255, 106, 106);
     pub const AQUAMARINE: Color = Color::new(127, 255, 212);
+    pub const VOID: Color = Color::new(200, 200, 200);
 }
 
 /// 点结构体
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub x: i32,           // x坐标
     pub y: i32,           // y坐标
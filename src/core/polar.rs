@@ -0,0 +1,168 @@
+//! 极坐标网格模块 - 圆形（同心环）迷宫的生成与邻接关系
+//!
+//! y 为环号（1 为圆心单元格，n 为最外环），x 为环内单元格序号（1..=环内单元格数，
+//! 顺时针递增）。环内单元格数随半径按 2 的幂增长，使单元格弧长大致均匀。
+//! 墙位于相邻单元格之间，打通的墙记录在 `Map::passages` 中。
+//!
+//! 方向约定：Up 为向内，Down 为向外，Left 为逆时针，Right 为顺时针。
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::core::error::MazeError;
+use crate::map::{CellState, Map, Edge, Topology, UnionFind};
+use crate::point::Direction;
+
+/// 第 ring 环（从 0 开始，0 为圆心）的单元格数
+pub fn ring_size(ring: i32) -> i32 {
+    if ring <= 0 {
+        1
+    } else {
+        // 6 × 不超过 ring 的最大 2 的幂
+        6 << (31 - ring.leading_zeros())
+    }
+}
+
+impl Map {
+    /// 创建 rings 个同心环的圆形迷宫，起点为圆心，终点位于最外环；环数非法时 panic
+    pub fn polar(rings: i32, width: i32, height: i32) -> Self {
+        Self::try_polar(rings, width, height).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// 创建圆形迷宫，环数少于 2 或过大时返回错误
+    pub fn try_polar(rings: i32, width: i32, height: i32) -> Result<Self, MazeError> {
+        let mut map = Self::polar_blank(rings, width, height)?;
+        map.generate();
+        Ok(map)
+    }
+
+    /// 检查环数并创建尚未生成迷宫的圆形地图
+    pub(crate) fn polar_blank(rings: i32, width: i32, height: i32) -> Result<Self, MazeError> {
+        // 环数超过 2^28 时最外环的单元格数超出 i32
+        if rings > 1 << 28 {
+            return Err(MazeError::InvalidDimensions { m: 0, n: rings, reason: "地图过大" });
+        }
+        let m = ring_size(rings.max(1) - 1);
+        Self::validate_dimensions(m, rings, 1, Topology::Polar)?;
+        Ok(Self::blank(m, rings, 1, Topology::Polar, width, height))
+    }
+
+    /// 第 y 环的单元格数
+    pub fn ring_cells(&self, y: i32) -> i32 {
+        ring_size(y - 1)
+    }
+
+    /// Kruskal + 并查集生成圆形完美迷宫
+    pub(crate) fn generate_polar(&mut self, rng: &mut impl Rng) {
+
+        let cells = self.cell_coords();
+        for &(x, y) in &cells {
+            self.grid[(x as usize, y as usize)] = CellState::Path;
+        }
+
+        // 每个单元格只收集顺时针与向外的边，避免重复
+        let mut edges: Vec<((i32, i32), (i32, i32))> = Vec::new();
+        for &(x, y) in &cells {
+            let count = self.ring_cells(y);
+            if count > 1 {
+                edges.push(((x, y), (x % count + 1, y)));
+            }
+            for child in self.polar_children(x, y) {
+                edges.push(((x, y), child));
+            }
+        }
+        edges.shuffle(rng);
+
+        let index: std::collections::HashMap<(i32, i32), usize> =
+            cells.iter().enumerate().map(|(i, &c)| (c, i)).collect();
+        let mut uf = UnionFind::new(cells.len());
+        let mut carved = Vec::new();
+        for (a, b) in edges {
+            let (ia, ib) = (index[&a], index[&b]);
+            if uf.find(ia) != uf.find(ib) {
+                uf.union(ia, ib);
+                carved.push((a, b));
+            }
+        }
+        for (a, b) in carved {
+            self.open_passage(a, b);
+        }
+    }
+
+    /// 外侧相邻环中与 (x, y) 共享边界的单元格
+    pub fn polar_children(&self, x: i32, y: i32) -> Vec<(i32, i32)> {
+        if y >= self.n {
+            return Vec::new();
+        }
+        let ratio = self.ring_cells(y + 1) / self.ring_cells(y);
+        ((x - 1) * ratio + 1..=x * ratio).map(|cx| (cx, y + 1)).collect()
+    }
+
+    /// 内侧相邻环中与 (x, y) 共享边界的单元格
+    pub fn polar_parent(&self, x: i32, y: i32) -> Option<(i32, i32)> {
+        if y <= 1 {
+            return None;
+        }
+        let ratio = self.ring_cells(y) / self.ring_cells(y - 1);
+        Some(((x - 1) / ratio + 1, y - 1))
+    }
+
+    /// 极坐标网格的邻接边（仅包含已打通的墙）
+    pub(crate) fn polar_neighbors(&self, x: i32, y: i32, edges: &mut Vec<Edge>) {
+        let count = self.ring_cells(y);
+        let mut candidates = Vec::with_capacity(6);
+        if count > 1 {
+            candidates.push((x % count + 1, y, Direction::Right));
+            candidates.push(((x + count - 2) % count + 1, y, Direction::Left));
+        }
+        if let Some((px, py)) = self.polar_parent(x, y) {
+            candidates.push((px, py, Direction::Up));
+        }
+        for (cx, cy) in self.polar_children(x, y) {
+            candidates.push((cx, cy, Direction::Down));
+        }
+        candidates.dedup_by_key(|c| (c.0, c.1));
+        let neighbors = candidates
+            .into_iter()
+            .filter(|&(nx, ny, _)| self.get_cell(nx, ny) != CellState::Wall && self.has_passage((x, y), (nx, ny)))
+            .map(|(nx, ny, direction)| Edge { x: nx, y: ny, direction, cost: 1 });
+        edges.extend(neighbors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_size() {
+        let sizes: Vec<i32> = (0..6).map(ring_size).collect();
+        assert_eq!(sizes, vec![1, 6, 12, 12, 24, 24]);
+    }
+
+    #[test]
+    fn test_polar_maze_is_spanning_tree() {
+        let map = Map::polar(6, 100, 100);
+        let cells = map.cell_coords().len();
+        assert_eq!(cells, 1 + 6 + 12 + 12 + 24 + 24);
+        assert_eq!(map.passages.len(), cells - 1);
+        for (x, y) in map.cell_coords() {
+            for edge in map.neighbors(x, y) {
+                assert_eq!(map.edge_cost(edge.x, edge.y, x, y), Some(1));
+            }
+        }
+        let (sx, sy) = map.start();
+        let (ex, ey) = map.end();
+        let result = crate::astar_search(&mut map.clone(), sx, sy, ex, ey);
+        assert!(result.found);
+        assert!(result.path_length >= ey - sy);
+    }
+
+    #[test]
+    fn test_try_polar_rejects_invalid_rings() {
+        for rings in [i32::MIN, 0, 1, i32::MAX] {
+            assert!(matches!(Map::try_polar(rings, 100, 100), Err(MazeError::InvalidDimensions { .. })));
+            assert!(Map::seeded(Topology::Polar, 0, rings, 7, 100, 100).is_err());
+        }
+        assert_eq!(Map::try_polar(2, 100, 100).unwrap().cell_coords().len(), 7);
+    }
+}
@@ -0,0 +1,142 @@
+//! 图片导入模块 - 将扫描的迷宫谜题、平面图等 PNG / BMP 图片转换为地图
+//!
+//! 图片按 `cell_pixels × cell_pixels` 的像素块划分，每块对应 grid 中的一个格子：
+//! 块内平均亮度低于阈值为墙，否则为通道。块内出现起点 / 终点标记色的像素时设为起点 / 终点，
+//! 块的平均颜色接近某个地形色时设置对应的地形权重。图片左上角的块对应 (1, 1)，
+//! 第 0 行、第 0 列自动补为外墙。
+
+use std::path::Path;
+use image::{DynamicImage, Rgb};
+use crate::core::error::MazeError;
+use crate::map::{CellState, Map, Topology};
+
+/// 图片导入参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageImport {
+    pub threshold: u8,                 // 亮度低于该值的块为墙
+    pub cell_pixels: u32,              // 每个格子对应的像素边长
+    pub start_color: Option<[u8; 3]>,  // 起点标记色
+    pub goal_color: Option<[u8; 3]>,   // 终点标记色
+    pub tolerance: u8,                 // 颜色匹配时每个通道允许的最大偏差
+    pub terrain: Vec<([u8; 3], i32)>,  // 地形色及对应的进入代价
+}
+
+impl Default for ImageImport {
+    fn default() -> Self {
+        Self {
+            threshold: 128,
+            cell_pixels: 1,
+            start_color: Some([255, 0, 0]),
+            goal_color: Some([0, 255, 0]),
+            tolerance: 40,
+            terrain: Vec::new(),
+        }
+    }
+}
+
+impl ImageImport {
+    fn matches(&self, color: Option<[u8; 3]>, pixel: [u8; 3]) -> bool {
+        color.is_some_and(|c| c.iter().zip(pixel.iter()).all(|(a, b)| a.abs_diff(*b) <= self.tolerance))
+    }
+}
+
+impl Map {
+    /// 从图片文件（PNG / BMP）加载地图
+    pub fn load_image(path: impl AsRef<Path>, options: &ImageImport) -> Result<Self, MazeError> {
+        let image = image::open(path).map_err(|e| MazeError::InvalidImage(e.to_string()))?;
+        Self::from_image(&image, options)
+    }
+
+    /// 将图片转换为地图；缺少起点 / 终点标记时取第一个 / 最后一个通道
+    pub fn from_image(image: &DynamicImage, options: &ImageImport) -> Result<Self, MazeError> {
+        let rgb = image.to_rgb8();
+        let block = options.cell_pixels.max(1);
+        let m = rgb.width().div_ceil(block) as i32;
+        let n = rgb.height().div_ceil(block) as i32;
+        Self::validate_dimensions(m, n, 1, Topology::Square)?;
+
+        let mut map = Self::blank(m, n, 1, Topology::Square, m * block as i32, n * block as i32);
+        let (mut start, mut goal) = (None, None);
+        for y in 1..=n {
+            for x in 1..=m {
+                let (x0, y0) = ((x - 1) as u32 * block, (y - 1) as u32 * block);
+                let pixels: Vec<[u8; 3]> = (y0..(y0 + block).min(rgb.height()))
+                    .flat_map(|py| (x0..(x0 + block).min(rgb.width())).map(move |px| (px, py)))
+                    .map(|(px, py)| {
+                        let Rgb(p) = *rgb.get_pixel(px, py);
+                        p
+                    })
+                    .collect();
+                let mut sum = [0u32; 3];
+                for p in &pixels {
+                    for c in 0..3 {
+                        sum[c] += p[c] as u32;
+                    }
+                }
+                let count = pixels.len() as u32;
+                let mean = sum.map(|v| (v / count) as u8);
+
+                if start.is_none() && pixels.iter().any(|&p| options.matches(options.start_color, p)) {
+                    start = Some((x, y));
+                } else if goal.is_none() && pixels.iter().any(|&p| options.matches(options.goal_color, p)) {
+                    goal = Some((x, y));
+                } else {
+                    let terrain = options.terrain.iter().find(|(c, _)| options.matches(Some(*c), mean));
+                    if let Some(&(_, weight)) = terrain {
+                        map.set_weight(x, y, weight);
+                    } else if luminance(mean) < options.threshold {
+                        continue;
+                    }
+                }
+                map.grid[(x as usize, y as usize)] = CellState::Path;
+            }
+        }
+
+        let open: Vec<(i32, i32)> = (1..=n)
+            .flat_map(|y| (1..=m).map(move |x| (x, y)))
+            .filter(|&(x, y)| map.is_passable(x, y))
+            .collect();
+        if let Some((sx, sy)) = start.or_else(|| open.first().copied()) {
+            (map.sx, map.sy) = (sx, sy);
+        }
+        if let Some((ex, ey)) = goal.or_else(|| open.last().copied()) {
+            (map.ex, map.ey) = (ex, ey);
+        }
+        Ok(map)
+    }
+}
+
+/// ITU-R BT.601 亮度
+fn luminance([r, g, b]: [u8; 3]) -> u8 {
+    ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbImage;
+
+    #[test]
+    fn test_from_image() {
+        // 2×2 像素为一格：四周为墙，中间一行通道，左端红色起点、右端绿色终点，中间一格为蓝色地形
+        let image = RgbImage::from_fn(10, 6, |px, py| {
+            match (px / 2, py / 2) {
+                (_, 0) | (_, 2) => Rgb([0, 0, 0]),
+                (0, 1) => Rgb([250, 10, 10]),
+                (4, 1) => Rgb([10, 250, 10]),
+                (2, 1) => Rgb([0, 0, 255]),
+                _ => Rgb([255, 255, 255]),
+            }
+        });
+        let options = ImageImport { cell_pixels: 2, terrain: vec![([0, 0, 255], 7)], ..Default::default() };
+        let map = Map::from_image(&DynamicImage::ImageRgb8(image), &options).unwrap();
+        assert_eq!((map.m, map.n), (5, 3));
+        assert_eq!(map.start(), (1, 2));
+        assert_eq!(map.end(), (5, 2));
+        assert!(!map.is_passable(3, 1));
+        assert_eq!(map.cell_weight(3, 2), 7);
+        let result = crate::astar_search(&mut map.clone(), 1, 2, 5, 2);
+        assert!(result.found);
+        assert_eq!(result.path_length, 1 + 7 + 1 + 1);
+    }
+}
@@ -0,0 +1,186 @@
+//! 序列化辅助 - 将哈希容器按键排序后输出为数组，保证 JSON 稳定且可比较
+//!
+//! JSON 对象的键只能是字符串，坐标键 `(x, y)` 因此输出为 `[[[x, y], value], ...]`。
+//! 反序列化地图时先读入 `RawMap`，检查 grid 大小与 m、n、floors 一致后再转换为 `Map`。
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::core::error::MazeError;
+use crate::core::grid::Grid;
+use crate::core::mask::Mask;
+use crate::map::{Map, Portal, Topology};
+use crate::point::Direction;
+
+/// `Map` 的反序列化中间结构，字段与 `Map` 相同
+#[derive(Deserialize)]
+pub(crate) struct RawMap {
+    sx: i32,
+    sy: i32,
+    ex: i32,
+    ey: i32,
+    m: i32,
+    n: i32,
+    width: i32,
+    height: i32,
+    grid: Grid,
+    portals: Vec<Portal>,
+    #[serde(with = "sorted_map")]
+    doors: HashMap<(i32, i32), Direction>,
+    floors: i32,
+    #[serde(with = "sorted_set")]
+    stairs: HashSet<(i32, i32)>,
+    topology: Topology,
+    #[serde(with = "sorted_set")]
+    passages: HashSet<((i32, i32), (i32, i32))>,
+    mask: Option<Mask>,
+    #[serde(with = "sorted_map")]
+    weights: HashMap<(i32, i32), i32>,
+}
+
+impl TryFrom<RawMap> for Map {
+    type Error = MazeError;
+
+    fn try_from(raw: RawMap) -> Result<Self, MazeError> {
+        let (m, n, floors) = (raw.m, raw.n, raw.floors);
+        let invalid = |reason| Err(MazeError::InvalidDimensions { m, n, reason });
+        if m < 0 || n < 0 || floors < 1 {
+            return invalid("宽高不能为负，楼层数至少为 1");
+        }
+        // grid 为 (m + 1) 列、(n + 1) × floors 行
+        let rows = (n as i64 + 1) * floors as i64;
+        if raw.grid.width() as i64 != m as i64 + 1 || raw.grid.height() as i64 != rows {
+            return invalid("grid 大小与地图尺寸不符");
+        }
+        if raw.mask.as_ref().is_some_and(|mask| (mask.m, mask.n) != (m, n)) {
+            return invalid("遮罩尺寸与地图尺寸不符");
+        }
+        Ok(Map {
+            sx: raw.sx,
+            sy: raw.sy,
+            ex: raw.ex,
+            ey: raw.ey,
+            m,
+            n,
+            width: raw.width,
+            height: raw.height,
+            grid: raw.grid,
+            portals: raw.portals,
+            doors: raw.doors,
+            floors,
+            stairs: raw.stairs,
+            topology: raw.topology,
+            passages: raw.passages,
+            mask: raw.mask,
+            weights: raw.weights,
+        })
+    }
+}
+
+/// `HashMap<K, V>` ⇄ 按键排序的 `[[key, value], ...]`
+pub(crate) mod sorted_map {
+    use super::*;
+
+    pub fn serialize<S, K, V>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        K: Serialize + Ord,
+        V: Serialize,
+    {
+        let mut entries: Vec<(&K, &V)> = map.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        entries.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, K, V>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        D: Deserializer<'de>,
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+    {
+        Ok(Vec::<(K, V)>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+
+/// `HashSet<T>` ⇄ 排序后的 `[item, ...]`
+pub(crate) mod sorted_set {
+    use super::*;
+
+    pub fn serialize<S, T>(set: &HashSet<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize + Ord,
+    {
+        let mut items: Vec<&T> = set.iter().collect();
+        items.sort();
+        items.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<HashSet<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de> + Eq + Hash,
+    {
+        Ok(Vec::<T>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Direction, Map, Point, Color};
+
+    #[test]
+    fn test_map_json_round_trip() {
+        let mut map = Map::with_size(10, 10, 100, 100);
+        map.add_portal((1, 1), (9, 9), 3);
+        map.add_door(2, 1, Direction::Right);
+        map.set_weight(3, 1, 5);
+
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, serde_json::to_string(&map.clone()).unwrap());
+        let loaded: Map = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.grid, map.grid);
+        assert_eq!(loaded.portals, map.portals);
+        assert_eq!(loaded.doors, map.doors);
+        assert_eq!(loaded.weights, map.weights);
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["doors"], serde_json::json!([[[2, 1], "Right"]]));
+        // grid 仍输出为按行嵌套的整数数组
+        assert_eq!(value["grid"].as_array().map(Vec::len), Some(11));
+        assert_eq!(value["grid"][0], serde_json::json!(vec![1; 11]));
+        assert_eq!(value["grid"][1][1], 0);
+    }
+
+    #[test]
+    fn test_mismatched_map_json_fails_to_load() {
+        let map = Map::with_size(10, 10, 100, 100);
+        let mut value = serde_json::to_value(&map).unwrap();
+        value["m"] = serde_json::json!(50);
+        let err = serde_json::from_value::<Map>(value).unwrap_err();
+        assert!(err.to_string().contains("grid 大小与地图尺寸不符"), "{}", err);
+
+        let mut value = serde_json::to_value(&map).unwrap();
+        value["floors"] = serde_json::json!(2);
+        assert!(serde_json::from_value::<Map>(value).is_err());
+
+        let masked = Map::with_mask(crate::Mask::circle(9, 9), crate::Topology::Square, 90, 90).unwrap();
+        let mut value = serde_json::to_value(&masked).unwrap();
+        value["mask"]["cells"].as_array_mut().unwrap().pop();
+        let err = serde_json::from_value::<Map>(value).unwrap_err();
+        assert!(err.to_string().contains("遮罩"), "{}", err);
+    }
+
+    #[test]
+    fn test_search_result_json() {
+        let mut map = Map::with_size(10, 10, 100, 100);
+        let result = crate::bfs_search(&mut map, 1, 1, 9, 9);
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["found"], true);
+        assert_eq!(value["path_length"], result.path_length);
+
+        let point = Point::with_color(1, 2, Color::YELLOW);
+        let json = serde_json::to_string(&point).unwrap();
+        assert_eq!(json, r#"{"x":1,"y":2,"step":0,"h_cost":0,"direction":"None","color":{"r":255,"g":255,"b":0}}"#);
+    }
+}
@@ -0,0 +1,138 @@
+//! 文本迷宫格式 - 便于在 git 中保存、比较的纯文本地图
+//!
+//! 每个字符对应 grid 中的一个坐标，第 y 行第 x 个字符即 (x, y)，行数与列数分别为 n + 1、m + 1：
+//!
+//! | 字符 | 含义 |
+//! |------|------|
+//! | `#` | 墙 |
+//! | `.` | 通道 |
+//! | `S` | 起点（通道） |
+//! | `G` | 终点（通道） |
+//! | `1`-`9` | 进入代价为该数字的通道（地形权重，`1` 等同于 `.`） |
+//!
+//! 第一行、第一列为外墙，只能是 `#`；较短的行在末尾补墙。缺少 `S` / `G` 时取第一个 / 最后一个通道。
+//! 只描述单层方形网格的墙与权重，传送门、单向门、楼梯和遮罩不会被保存。
+//!
+//! ```text
+//! #######
+//! #S..#.#
+//! #.#.#.#
+//! #.#9..#
+//! #...#G#
+//! #######
+//! ```
+
+use crate::core::error::MazeError;
+use crate::map::{CellState, Map, Topology};
+
+impl Map {
+    /// 从文本格式解析地图，width / height 为像素尺寸
+    pub fn from_text(text: &str, width: i32, height: i32) -> Result<Self, MazeError> {
+        let mut rows: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+        while rows.last().is_some_and(|row| row.is_empty()) {
+            rows.pop();
+        }
+        let cols = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let (m, n) = (cols as i32 - 1, rows.len() as i32 - 1);
+        Self::validate_dimensions(m, n, 1, Topology::Square)?;
+
+        let mut map = Self::blank(m, n, 1, Topology::Square, width, height);
+        let (mut start, mut goal) = (None, None);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                let error = |reason| Err(MazeError::InvalidText { line: y + 1, column: x + 1, reason });
+                let (cx, cy) = (x as i32, y as i32);
+                let weight = match c {
+                    '#' => continue,
+                    '.' | 'S' | 'G' => 1,
+                    '1'..='9' => c as i32 - '0' as i32,
+                    _ => return error("未知字符"),
+                };
+                if x == 0 || y == 0 {
+                    return error("第一行、第一列必须为墙");
+                }
+                let slot = match c {
+                    'S' => Some(&mut start),
+                    'G' => Some(&mut goal),
+                    _ => None,
+                };
+                if let Some(slot) = slot {
+                    if slot.replace((cx, cy)).is_some() {
+                        return error("起点 S / 终点 G 重复");
+                    }
+                }
+                map.grid[(x, y)] = CellState::Path;
+                map.set_weight(cx, cy, weight);
+            }
+        }
+
+        let open: Vec<(i32, i32)> = (1..=n)
+            .flat_map(|y| (1..=m).map(move |x| (x, y)))
+            .filter(|&(x, y)| map.is_passable(x, y))
+            .collect();
+        if let Some((sx, sy)) = start.or_else(|| open.first().copied()) {
+            (map.sx, map.sy) = (sx, sy);
+        }
+        if let Some((ex, ey)) = goal.or_else(|| open.last().copied()) {
+            (map.ex, map.ey) = (ex, ey);
+        }
+        Ok(map)
+    }
+
+    /// 导出为文本格式（搜索状态视为通道，虚空视为墙）
+    pub fn to_text(&self) -> String {
+        let mut text = String::with_capacity(self.grid.height() * (self.grid.width() + 1));
+        for (y, row) in self.grid.rows().enumerate() {
+            for (x, &v) in row.iter().enumerate() {
+                let (cx, cy) = (x as i32, y as i32);
+                let c = if v == CellState::Wall || self.is_void(cx, cy) {
+                    '#'
+                } else if (cx, cy) == self.start() {
+                    'S'
+                } else if (cx, cy) == self.end() {
+                    'G'
+                } else {
+                    match self.cell_weight(cx, cy) {
+                        w @ 2..=9 => char::from(b'0' + w as u8),
+                        _ => '.',
+                    }
+                };
+                text.push(c);
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_round_trip() {
+        let map = Map::with_size(11, 7, 110, 70);
+        let text = map.to_text();
+        let loaded = Map::from_text(&text, 110, 70).unwrap();
+        assert_eq!((loaded.m, loaded.n), (11, 7));
+        assert_eq!(loaded.start(), map.start());
+        assert_eq!(loaded.end(), map.end());
+        assert_eq!(loaded.grid, map.grid);
+        assert_eq!(loaded.to_text(), text);
+    }
+
+    #[test]
+    fn test_text_weights_and_errors() {
+        let map = Map::from_text("#####\n#S9G#\n#...#\n#####\n", 50, 40).unwrap();
+        assert_eq!(map.cell_weight(2, 1), 9);
+        assert_eq!(map.edge_cost(1, 1, 2, 1), Some(9));
+        let result = crate::astar_search(&mut map.clone(), 1, 1, 3, 1);
+        assert_eq!(result.path_length, 4);
+
+        assert!(matches!(
+            Map::from_text("###\n#x#\n###", 30, 30),
+            Err(MazeError::InvalidText { line: 2, column: 2, .. })
+        ));
+        assert!(Map::from_text("#S#\n#S#\n###", 30, 30).is_err());
+    }
+}
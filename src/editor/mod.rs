@@ -0,0 +1,162 @@
+use eframe::egui;
+use crate::{config::CELL_COLORS, CellState, Map};
+
+// 撤销栈上限，避免大地图快照占用过多内存
+const HISTORY_LIMIT: usize = 100;
+
+/// 可拖动的标记
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Marker {
+    Start,
+    Goal,
+}
+
+/// 画布编辑器：左键拖动画墙、右键拖动擦除、拖动起点/终点标记，支持撤销/重做
+///
+/// 仅用于方形网格（Square / Torus），编辑结果写入 `MazeApp::original_map`。
+#[derive(Default)]
+pub struct Editor {
+    pub enabled: bool,
+    undo: Vec<Map>,
+    redo: Vec<Map>,
+    stroke: bool,           // 当前是否处于一次按下-拖动-松开的笔画中
+    pending: Option<Map>,   // 笔画开始时的快照，首次产生修改时压入撤销栈
+    marker: Option<Marker>, // 正在拖动的标记
+}
+
+impl Editor {
+    /// 处理画布上的指针输入并绘制起点/终点标记，地图被修改时返回 true
+    pub fn handle(&mut self, ui: &egui::Ui, response: &egui::Response, map: &mut Map, cell_size: f32, floor: i32) -> bool {
+        let rect = response.rect;
+        let to_screen = |x: i32, y: i32| egui::pos2(
+            rect.min.x + (x as f32 + 0.5) * cell_size,
+            rect.min.y + ((y - map.floor_offset(floor)) as f32 + 0.5) * cell_size,
+        );
+        let painter = ui.painter_at(rect);
+        for (x, y, label) in [(map.sx, map.sy, "S"), (map.ex, map.ey, "G")] {
+            if map.floor_of(y) == floor {
+                let center = to_screen(x, y);
+                painter.circle(center, cell_size * 0.5, CELL_COLORS[4], egui::Stroke::new(1.0, CELL_COLORS[1]));
+                painter.text(center, egui::Align2::CENTER_CENTER, label, egui::FontId::monospace(cell_size * 0.8), CELL_COLORS[1]);
+            }
+        }
+
+        if !response.is_pointer_button_down_on() {
+            self.stroke = false;
+            self.pending = None;
+            self.marker = None;
+            return false;
+        }
+        let Some(pos) = response.interact_pointer_pos() else {
+            return false;
+        };
+        let x = ((pos.x - rect.min.x) / cell_size).floor() as i32;
+        let y = ((pos.y - rect.min.y) / cell_size).floor() as i32 + map.floor_offset(floor);
+        if !map.in_bounds(x, y) {
+            return false;
+        }
+        let (primary, secondary) = ui.input(|i| (i.pointer.primary_down(), i.pointer.secondary_down()));
+
+        if !self.stroke {
+            self.stroke = true;
+            self.pending = Some(map.clone());
+            self.marker = match (x, y) {
+                _ if !primary => None,
+                p if p == map.start() => Some(Marker::Start),
+                p if p == map.end() => Some(Marker::Goal),
+                _ => None,
+            };
+        }
+        let changed = self.apply(map, x, y, primary, secondary);
+        if changed {
+            if let Some(snapshot) = self.pending.take() {
+                self.push_history(snapshot);
+            }
+        }
+        changed
+    }
+
+    /// 将一次指针位置应用到地图：移动标记、画墙或擦除
+    fn apply(&self, map: &mut Map, x: i32, y: i32, primary: bool, secondary: bool) -> bool {
+        match self.marker {
+            Some(marker) => {
+                let occupied = match marker {
+                    Marker::Start => (x, y) == map.end(),
+                    Marker::Goal => (x, y) == map.start(),
+                };
+                if !map.is_passable(x, y) || occupied {
+                    return false;
+                }
+                let target = match marker {
+                    Marker::Start => (&mut map.sx, &mut map.sy),
+                    Marker::Goal => (&mut map.ex, &mut map.ey),
+                };
+                if (*target.0, *target.1) == (x, y) {
+                    return false;
+                }
+                (*target.0, *target.1) = (x, y);
+                true
+            }
+            None => {
+                // 起点/终点所在格子始终保持可通行
+                if (x, y) == map.start() || (x, y) == map.end() {
+                    return false;
+                }
+                let value = match (primary, secondary) {
+                    (true, _) => CellState::Wall,
+                    (_, true) => CellState::Path,
+                    _ => return false,
+                };
+                if map.get_cell(x, y) == value {
+                    return false;
+                }
+                map.set_cell(x, y, value);
+                true
+            }
+        }
+    }
+
+    /// 撤销上一次编辑
+    pub fn undo(&mut self, map: &mut Map) -> bool {
+        match self.undo.pop() {
+            Some(previous) => {
+                self.redo.push(std::mem::replace(map, previous));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 重做上一次撤销的编辑
+    pub fn redo(&mut self, map: &mut Map) -> bool {
+        match self.redo.pop() {
+            Some(next) => {
+                self.undo.push(std::mem::replace(map, next));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// 清空编辑历史（生成新地图时调用）
+    pub fn clear_history(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    fn push_history(&mut self, snapshot: Map) {
+        if self.undo.len() >= HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+        self.undo.push(snapshot);
+        self.redo.clear();
+    }
+}
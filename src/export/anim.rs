@@ -0,0 +1,131 @@
+//! 动画导出 - 将搜索轨迹逐步应用到地图并渲染为 GIF 动画或编号的 PNG 帧序列
+//!
+//! 步骤语义与 GUI 回放相同（`Map::apply_step`）：第一帧为初始地图，之后每 stride 步输出一帧，
+//! 最后一帧为全部步骤完成后的地图，并按 `ExportOptions` 叠加最终路径。
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, DynamicImage, Frame, RgbImage};
+use crate::core::error::MazeError;
+use crate::map::Map;
+use crate::point::Point;
+use super::{ExportOptions, check_topology, render_image};
+
+/// 动画参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationOptions {
+    pub stride: usize, // 每帧之间应用的步数
+    pub delay_ms: u32, // 每帧显示时长（毫秒）
+    pub hold_ms: u32,  // 最后一帧显示时长（毫秒）
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        Self { stride: 10, delay_ms: 50, hold_ms: 2000 }
+    }
+}
+
+/// 依次渲染搜索过程的每一帧，frame(序号, 图片, 是否为最后一帧)
+///
+/// map 为搜索前的地图，steps 为 `run_algorithm` 返回的步骤。返回输出的帧数。
+pub fn render_frames(
+    map: &Map,
+    steps: &[Point],
+    options: &ExportOptions,
+    animation: &AnimationOptions,
+    mut frame: impl FnMut(usize, RgbImage, bool) -> Result<(), MazeError>,
+) -> Result<usize, MazeError> {
+    check_topology(map)?;
+    let stride = animation.stride.max(1);
+    let path = map.trace_path(steps);
+    let searching = ExportOptions { show_path: false, ..*options };
+
+    let mut current = map.clone();
+    let mut count = 0;
+    for chunk in steps.chunks(stride) {
+        frame(count, render_image(&current, &[], &searching)?, false)?;
+        count += 1;
+        chunk.iter().for_each(|step| current.apply_step(step));
+    }
+    frame(count, render_image(&current, &path, options)?, true)?;
+    Ok(count + 1)
+}
+
+/// 将搜索过程导出为循环播放的 GIF 动画，返回帧数
+pub fn export_gif(
+    map: &Map,
+    steps: &[Point],
+    options: &ExportOptions,
+    animation: &AnimationOptions,
+    file: impl AsRef<Path>,
+) -> Result<usize, MazeError> {
+    let io = |e: std::io::Error| MazeError::Io(e.to_string());
+    let encode = |e: image::ImageError| MazeError::Io(e.to_string());
+    let writer = BufWriter::new(File::create(file).map_err(io)?);
+    let mut encoder = GifEncoder::new_with_speed(writer, 10);
+    encoder.set_repeat(Repeat::Infinite).map_err(encode)?;
+    render_frames(map, steps, options, animation, |_, image, last| {
+        let delay = if last { animation.hold_ms } else { animation.delay_ms };
+        let rgba = DynamicImage::ImageRgb8(image).into_rgba8();
+        encoder
+            .encode_frame(Frame::from_parts(rgba, 0, 0, Delay::from_numer_denom_ms(delay, 1)))
+            .map_err(encode)
+    })
+}
+
+/// 将搜索过程导出为 dir 目录下编号的 PNG 帧（frame_0000.png, frame_0001.png, ...），返回帧数
+pub fn export_frames(
+    map: &Map,
+    steps: &[Point],
+    options: &ExportOptions,
+    animation: &AnimationOptions,
+    dir: impl AsRef<Path>,
+) -> Result<usize, MazeError> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir).map_err(|e| MazeError::Io(e.to_string()))?;
+    render_frames(map, steps, options, animation, |index, image, _| {
+        image
+            .save_with_format(dir.join(format!("frame_{:04}.png", index)), image::ImageFormat::Png)
+            .map_err(|e| MazeError::Io(e.to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Color;
+    use crate::export::PATH_COLOR;
+
+    #[test]
+    fn test_render_frames() {
+        let map = Map::with_size(10, 10, 100, 100);
+        let (steps, found, _) = crate::run_algorithm(&mut map.clone(), crate::Algorithm::BFS);
+        assert!(found);
+        let options = ExportOptions { scale: 2, ..Default::default() };
+        let animation = AnimationOptions { stride: 7, ..Default::default() };
+
+        let mut frames = Vec::new();
+        let count = render_frames(&map, &steps, &options, &animation, |index, image, last| {
+            frames.push((index, image, last));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(count, steps.len().div_ceil(7) + 1);
+        assert_eq!(frames.len(), count);
+        assert!(frames.iter().enumerate().all(|(i, (index, _, last))| *index == i && *last == (i + 1 == count)));
+
+        // 第一帧为初始地图，最后一帧与逐步回放后的地图一致
+        let (sx, sy) = map.start();
+        assert_eq!(frames[0].1.get_pixel(sx as u32 * 2, sy as u32 * 2).0, [255, 255, 255]);
+        let mut replayed = map.clone();
+        steps.iter().for_each(|step| replayed.apply_step(step));
+        let last = &frames[count - 1].1;
+        let (ex, ey) = map.end();
+        let yellow = Color::YELLOW;
+        assert_eq!(replayed.get_cell(ex, ey), crate::CellState::Start);
+        assert_eq!(last.get_pixel(ex as u32 * 2, ey as u32 * 2).0, [yellow.r, yellow.g, yellow.b]);
+        assert!(last.pixels().any(|p| p.0 == [PATH_COLOR.r, PATH_COLOR.g, PATH_COLOR.b]));
+    }
+}
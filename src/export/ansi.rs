@@ -0,0 +1,119 @@
+//! 终端（ANSI）渲染 - 在终端中以 24 位色和 Unicode 半块字符绘制地图，并可原地播放搜索过程
+//!
+//! 每个字符为上下两个格子：前景色为上半格 `▀`，背景色为下半格，颜色与 GUI 的 `CELL_COLORS` 相同。
+//! 动画每帧将光标移回左上角后重绘，适合通过 SSH 在没有图形界面的机器上演示。
+
+use std::fmt::Write as _;
+use std::io::Write;
+use std::time::Duration;
+use crate::core::error::MazeError;
+use crate::map::Map;
+use crate::point::{Color, Point};
+use super::{AnimationOptions, check_floor, check_topology};
+
+const RESET: &str = "\x1b[0m";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+
+/// 将地图的 floor 层渲染为带 ANSI 颜色的文本，每行以换行结束
+pub fn render_ansi(map: &Map, floor: i32) -> Result<String, MazeError> {
+    check_topology(map)?;
+    check_floor(map, floor)?;
+    let base = map.floor_offset(floor);
+    let rows = map.n + 1;
+    let mut out = String::new();
+    for y in (0..rows).step_by(2) {
+        for x in 0..=map.m {
+            let top = map.cell_color(x, base + y);
+            // 行数为奇数时最后一行的下半格留空
+            let bottom = (y + 1 < rows).then(|| map.cell_color(x, base + y + 1));
+            let Color { r, g, b } = top;
+            let _ = write!(out, "\x1b[38;2;{};{};{}m", r, g, b);
+            match bottom {
+                Some(Color { r, g, b }) => {
+                    let _ = write!(out, "\x1b[48;2;{};{};{}m", r, g, b);
+                }
+                None => out.push_str("\x1b[49m"),
+            }
+            out.push('▀');
+        }
+        out.push_str(RESET);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// 在终端中原地播放搜索过程，每 stride 步重绘一次，返回绘制的帧数
+///
+/// map 为搜索前的地图，steps 为 `run_algorithm` 返回的步骤，步骤语义与 GUI 回放相同。
+pub fn animate_ansi(
+    map: &Map,
+    steps: &[Point],
+    floor: i32,
+    animation: &AnimationOptions,
+    out: &mut impl Write,
+) -> Result<usize, MazeError> {
+    check_topology(map)?;
+    check_floor(map, floor)?;
+    // 出错时也要恢复光标
+    write!(out, "{}", HIDE_CURSOR).map_err(io)?;
+    let result = play(map, steps, floor, animation, out);
+    write!(out, "{}", SHOW_CURSOR).map_err(io)?;
+    out.flush().map_err(io)?;
+    result
+}
+
+fn play(map: &Map, steps: &[Point], floor: i32, animation: &AnimationOptions, out: &mut impl Write) -> Result<usize, MazeError> {
+    let lines = (map.n + 2) / 2;
+    let mut current = map.clone();
+    let mut count = 0;
+    let chunks = steps.chunks(animation.stride.max(1)).map(Some).chain([None]);
+    for chunk in chunks {
+        if count > 0 {
+            write!(out, "\x1b[{}A", lines).map_err(io)?;
+        }
+        out.write_all(render_ansi(&current, floor)?.as_bytes()).map_err(io)?;
+        out.flush().map_err(io)?;
+        count += 1;
+        let delay = if chunk.is_some() { animation.delay_ms } else { animation.hold_ms };
+        std::thread::sleep(Duration::from_millis(delay as u64));
+        chunk.into_iter().flatten().for_each(|step| current.apply_step(step));
+    }
+    Ok(count)
+}
+
+fn io(e: std::io::Error) -> MazeError {
+    MazeError::Io(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_ansi() {
+        let map = Map::with_size(10, 6, 100, 60);
+        let text = render_ansi(&map, 0).unwrap();
+        // 7 行格子合并为 4 行字符，每行 11 个半块字符
+        assert_eq!(text.lines().count(), 4);
+        assert!(text.lines().all(|line| line.matches('▀').count() == 11 && line.ends_with(RESET)));
+        // 左上角上下两格都是墙
+        assert!(text.starts_with("\x1b[38;2;0;10;0m\x1b[48;2;0;10;0m▀"));
+        // 最后一行只有上半格
+        assert!(text.lines().last().unwrap().contains("\x1b[49m"));
+
+        let mut out = Vec::new();
+        let (steps, _, _) = crate::run_algorithm(&mut map.clone(), crate::Algorithm::BFS);
+        let animation = AnimationOptions { stride: 5, delay_ms: 0, hold_ms: 0 };
+        let frames = animate_ansi(&map, &steps, 0, &animation, &mut out).unwrap();
+        assert_eq!(frames, steps.len().div_ceil(5) + 1);
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("\x1b[4A").count(), frames - 1);
+        assert!(out.starts_with(HIDE_CURSOR) && out.ends_with(SHOW_CURSOR));
+
+        assert_eq!(render_ansi(&map, -1).unwrap_err(), MazeError::InvalidFloor { floor: -1, floors: 1 });
+        let mut out = Vec::new();
+        assert!(animate_ansi(&map, &steps, 1, &animation, &mut out).is_err());
+        assert!(out.is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+//! 导出模块 - 将地图及搜索结果输出为图片
+//!
+//! 仅支持方形网格（Square / Torus），绘制内容与 GUI 的 `render::draw_maze` 保持一致：
+//! 格子按状态着色，最终路径以棕色虚线表示，每隔三段绘制一个方向箭头。
+//! PNG 为逐格填充的位图，SVG 为矢量图（墙为线段、路径为折线），适合打印；
+//! GIF / PNG 帧序列按 GUI 的回放语义逐步渲染整个搜索过程；ANSI 文本用于在终端中显示和播放。
+
+pub mod png;
+pub mod svg;
+pub mod anim;
+pub mod ansi;
+
+pub use self::png::{render_image, export_png};
+pub use self::svg::{render_svg, export_svg};
+pub use self::anim::{AnimationOptions, render_frames, export_gif, export_frames};
+pub use self::ansi::{render_ansi, animate_ansi};
+
+use image::{Rgb, RgbImage};
+use crate::core::error::MazeError;
+use crate::map::{CellState, Map, Topology};
+use crate::point::{Color, Direction};
+
+/// 路径虚线颜色（与 GUI 一致）
+pub const PATH_COLOR: Color = Color::new(139, 69, 19);
+
+/// 导出参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExportOptions {
+    pub scale: u32,         // 每个格子的像素边长
+    pub show_path: bool,    // 是否叠加路径虚线
+    pub show_arrows: bool,  // 是否在路径上绘制方向箭头
+    pub show_visited: bool, // 是否按搜索状态（已访问、回溯、最终路径）着色
+    pub floor: i32,         // 导出的楼层
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self { scale: 10, show_path: true, show_arrows: true, show_visited: true, floor: 0 }
+    }
+}
+
+/// 导出时格子的颜色；不显示搜索状态时已访问、回溯、最终路径均按通道着色
+pub(crate) fn cell_fill(map: &Map, x: i32, y: i32, options: &ExportOptions) -> Color {
+    match map.grid[(x as usize, y as usize)] {
+        CellState::Visited | CellState::Backtrack | CellState::Solution if !options.show_visited && !map.is_void(x, y) => Color::WHITE,
+        _ => map.cell_color(x, y),
+    }
+}
+
+/// 方向对应的单位向量（屏幕坐标，y 向下），不在平面内移动的方向返回 None
+pub fn direction_vector(direction: Direction) -> Option<(f32, f32)> {
+    match direction {
+        Direction::Up => Some((0.0, -1.0)),
+        Direction::Down => Some((0.0, 1.0)),
+        Direction::Left => Some((-1.0, 0.0)),
+        Direction::Right => Some((1.0, 0.0)),
+        Direction::UpLeft => Some((-0.5, -0.866)),
+        Direction::UpRight => Some((0.5, -0.866)),
+        Direction::DownLeft => Some((-0.5, 0.866)),
+        Direction::DownRight => Some((0.5, 0.866)),
+        Direction::None | Direction::Portal | Direction::Ascend | Direction::Descend => None,
+    }
+}
+
+/// 路径中的一段（坐标为层内坐标）
+pub(crate) struct PathSegment {
+    pub from: (i32, i32),
+    pub to: (i32, i32),
+    pub direction: Direction,
+    pub index: usize, // 在整条路径中的段序号
+}
+
+/// 路径中在 floor 层内、相邻两格之间的段
+///
+/// 与 GUI 一致，跳过传送门、换层以及跨越环面接缝的一步。
+pub(crate) fn path_segments(map: &Map, path: &[(i32, i32, Direction)], floor: i32) -> Vec<PathSegment> {
+    let on_floor = |y: i32| map.floor_of(y) == floor;
+    let base = map.floor_offset(floor);
+    path.windows(2)
+        .enumerate()
+        .filter_map(|(i, pair)| {
+            let ((x1, y1, dir), (x2, y2, _)) = (pair[0], pair[1]);
+            let planar = !matches!(dir, Direction::Portal | Direction::Ascend | Direction::Descend);
+            let adjacent = (x2 - x1).abs() + (y2 - y1).abs() == 1;
+            (planar && adjacent && on_floor(y1) && on_floor(y2))
+                .then_some(PathSegment { from: (x1, y1 - base), to: (x2, y2 - base), direction: dir, index: i })
+        })
+        .collect()
+}
+
+/// 检查导出是否支持该地图
+pub(crate) fn check_topology(map: &Map) -> Result<(), MazeError> {
+    match map.topology {
+        Topology::Square | Topology::Torus => Ok(()),
+        topology => Err(MazeError::UnsupportedTopology(topology)),
+    }
+}
+
+/// 检查楼层是否存在
+pub(crate) fn check_floor(map: &Map, floor: i32) -> Result<(), MazeError> {
+    if (0..map.floors).contains(&floor) {
+        Ok(())
+    } else {
+        Err(MazeError::InvalidFloor { floor, floors: map.floors })
+    }
+}
+
+/// 在 RgbImage 上绘制简单图元
+pub(crate) struct Canvas<'a> {
+    pub image: &'a mut RgbImage,
+}
+
+impl Canvas<'_> {
+    fn put(&mut self, x: i64, y: i64, color: Color) {
+        if x >= 0 && y >= 0 && (x as u32) < self.image.width() && (y as u32) < self.image.height() {
+            self.image.put_pixel(x as u32, y as u32, Rgb([color.r, color.g, color.b]));
+        }
+    }
+
+    /// 填充矩形 [x0, x1) × [y0, y1)
+    pub fn fill_rect(&mut self, x0: u32, y0: u32, x1: u32, y1: u32, color: Color) {
+        for y in y0..y1 {
+            for x in x0..x1 {
+                self.put(x as i64, y as i64, color);
+            }
+        }
+    }
+
+    /// 绘制宽度为 width 的线段
+    pub fn line(&mut self, a: (f32, f32), b: (f32, f32), width: f32, color: Color) {
+        let r = (width / 2.0).max(0.5);
+        let (min_x, max_x) = (a.0.min(b.0) - r, a.0.max(b.0) + r);
+        let (min_y, max_y) = (a.1.min(b.1) - r, a.1.max(b.1) + r);
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        let len2 = (dx * dx + dy * dy).max(f32::EPSILON);
+        for y in min_y.floor() as i64..=max_y.ceil() as i64 {
+            for x in min_x.floor() as i64..=max_x.ceil() as i64 {
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                let t = (((px - a.0) * dx + (py - a.1) * dy) / len2).clamp(0.0, 1.0);
+                let (qx, qy) = (a.0 + dx * t - px, a.1 + dy * t - py);
+                if qx * qx + qy * qy <= r * r {
+                    self.put(x, y, color);
+                }
+            }
+        }
+    }
+
+    /// 填充三角形
+    pub fn triangle(&mut self, p: [(f32, f32); 3], color: Color) {
+        let edge = |a: (f32, f32), b: (f32, f32), c: (f32, f32)| (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+        let area = edge(p[0], p[1], p[2]);
+        if area == 0.0 {
+            return;
+        }
+        let min_x = p.iter().map(|q| q.0).fold(f32::MAX, f32::min).floor() as i64;
+        let max_x = p.iter().map(|q| q.0).fold(f32::MIN, f32::max).ceil() as i64;
+        let min_y = p.iter().map(|q| q.1).fold(f32::MAX, f32::min).floor() as i64;
+        let max_y = p.iter().map(|q| q.1).fold(f32::MIN, f32::max).ceil() as i64;
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let q = (x as f32 + 0.5, y as f32 + 0.5);
+                let w = [edge(p[1], p[2], q), edge(p[2], p[0], q), edge(p[0], p[1], q)];
+                if w.iter().all(|v| v * area >= 0.0) {
+                    self.put(x, y, color);
+                }
+            }
+        }
+    }
+
+    /// 在 center 处绘制指向 direction 的三角箭头
+    pub fn arrow(&mut self, center: (f32, f32), direction: Direction, size: f32, color: Color) {
+        if let Some(points) = arrow_points(center, direction, size) {
+            self.triangle(points, color);
+        }
+    }
+}
+
+/// 在 center 处指向 direction 的三角箭头顶点（几何与 GUI 的 draw_arrow 相同）
+pub(crate) fn arrow_points(center: (f32, f32), direction: Direction, size: f32) -> Option<[(f32, f32); 3]> {
+    let (dx, dy) = direction_vector(direction)?;
+    let (cx, cy) = center;
+    let tip = (cx + dx * size, cy + dy * size);
+    let left = (cx - dy * size * 0.5 - dx * size, cy + dx * size * 0.5 - dy * size);
+    let right = (cx + dy * size * 0.5 - dx * size, cy - dx * size * 0.5 - dy * size);
+    Some([tip, left, right])
+}
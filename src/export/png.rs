@@ -0,0 +1,95 @@
+//! PNG 导出
+
+use std::path::Path;
+use image::RgbImage;
+use crate::core::error::MazeError;
+use crate::map::Map;
+use crate::point::Direction;
+use super::{Canvas, ExportOptions, PATH_COLOR, cell_fill, check_floor, check_topology, path_segments};
+
+/// 将地图（指定楼层）及路径栅格化为图片
+///
+/// path 为最终路径上的格子及离开该格子的方向，与 GUI 中的 `path_points` 相同。
+pub fn render_image(map: &Map, path: &[(i32, i32, Direction)], options: &ExportOptions) -> Result<RgbImage, MazeError> {
+    check_topology(map)?;
+    check_floor(map, options.floor)?;
+    let scale = options.scale.max(1);
+    let (cols, rows) = ((map.m + 1) as u32, (map.n + 1) as u32);
+    let mut image = RgbImage::new(cols * scale, rows * scale);
+    let mut canvas = Canvas { image: &mut image };
+
+    let base = map.floor_offset(options.floor);
+    for y in 0..rows {
+        for x in 0..cols {
+            let color = cell_fill(map, x as i32, base + y as i32, options);
+            canvas.fill_rect(x * scale, y * scale, (x + 1) * scale, (y + 1) * scale, color);
+        }
+    }
+
+    if options.show_path {
+        // 与 GUI 相同的比例：CELL_SIZE 为 10 时虚线 3 像素、间隔 2 像素、线宽 1.5 像素、箭头 3 像素
+        let s = scale as f32;
+        let center = |(x, y): (i32, i32)| ((x as f32 + 0.5) * s, (y as f32 + 0.5) * s);
+        let (dash, gap) = (0.3 * s, 0.2 * s);
+        for segment in path_segments(map, path, options.floor) {
+            let (start, end) = (center(segment.from), center(segment.to));
+            let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+            let len = (dx * dx + dy * dy).sqrt();
+            let mut t = 0.0;
+            while t < len {
+                let (t0, t1) = (t / len, ((t + dash) / len).min(1.0));
+                let p1 = (start.0 + dx * t0, start.1 + dy * t0);
+                let p2 = (start.0 + dx * t1, start.1 + dy * t1);
+                canvas.line(p1, p2, 0.15 * s, PATH_COLOR);
+                t += dash + gap;
+            }
+            if options.show_arrows && segment.index % 3 == 0 {
+                let mid = ((start.0 + end.0) / 2.0, (start.1 + end.1) / 2.0);
+                canvas.arrow(mid, segment.direction, 0.3 * s, PATH_COLOR);
+            }
+        }
+    }
+    Ok(image)
+}
+
+/// 将地图及路径导出为 PNG 文件
+pub fn export_png(
+    map: &Map,
+    path: &[(i32, i32, Direction)],
+    options: &ExportOptions,
+    file: impl AsRef<Path>,
+) -> Result<(), MazeError> {
+    render_image(map, path, options)?
+        .save_with_format(file, image::ImageFormat::Png)
+        .map_err(|e| MazeError::Io(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Color;
+
+    #[test]
+    fn test_render_image_with_path() {
+        let map = Map::with_size(10, 10, 100, 100);
+        let path = [(1, 1, Direction::Right), (2, 1, Direction::Right), (3, 1, Direction::None)];
+        let options = ExportOptions { scale: 10, ..Default::default() };
+        let image = render_image(&map, &path, &options).unwrap();
+        assert_eq!(image.dimensions(), (110, 110));
+        let pixel = |x: u32, y: u32| image.get_pixel(x, y).0;
+        let black = Color::BLACK;
+        assert_eq!(pixel(0, 0), [black.r, black.g, black.b]);
+        // 第一段虚线从 (1, 1) 的中心出发
+        assert_eq!(pixel(16, 15), [PATH_COLOR.r, PATH_COLOR.g, PATH_COLOR.b]);
+
+        let plain = render_image(&map, &path, &ExportOptions { show_path: false, ..options }).unwrap();
+        assert_eq!(plain.get_pixel(16, 15).0, [255, 255, 255]);
+
+        let hex = Map::hex(5, 5, 100, 100);
+        assert!(matches!(render_image(&hex, &[], &options), Err(MazeError::UnsupportedTopology(_))));
+        for floor in [-1, 1] {
+            let result = render_image(&map, &path, &ExportOptions { floor, ..options });
+            assert_eq!(result.unwrap_err(), MazeError::InvalidFloor { floor, floors: 1 });
+        }
+    }
+}
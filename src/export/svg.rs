@@ -0,0 +1,190 @@
+//! SVG 导出
+//!
+//! 以格子边长 scale 为单位：墙为连接相邻墙格中心的细线段（水平 / 竖直的连续墙格合并为一条），
+//! 非通道格子（起终点、已访问等）以矩形着色，最终路径为一条或多条虚线折线。
+
+use std::fmt::Write;
+use std::path::Path;
+use crate::core::error::MazeError;
+use crate::map::{CellState, Map};
+use crate::point::{Color, Direction};
+use super::{ExportOptions, PATH_COLOR, arrow_points, cell_fill, check_floor, check_topology, path_segments};
+
+/// 墙的线宽（格子边长的比例）
+const WALL_WIDTH: f32 = 0.3;
+
+/// 保留两位小数，去掉多余的 0
+fn num(v: f32) -> String {
+    format!("{}", (v * 100.0).round() / 100.0)
+}
+
+/// 将地图（指定楼层）及路径生成为 SVG 文本
+pub fn render_svg(map: &Map, path: &[(i32, i32, Direction)], options: &ExportOptions) -> Result<String, MazeError> {
+    check_topology(map)?;
+    check_floor(map, options.floor)?;
+    let s = options.scale.max(1) as f32;
+    let (cols, rows) = (map.m + 1, map.n + 1);
+    let base = map.floor_offset(options.floor);
+    let is_wall = |x: i32, y: i32| map.grid[(x as usize, (base + y) as usize)] == CellState::Wall && !map.is_void(x, base + y);
+
+    let mut svg = String::new();
+    let (width, height) = (num(cols as f32 * s), num(rows as f32 * s));
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="{}"/>"#, Color::WHITE.to_hex());
+
+    // 格子着色
+    let _ = writeln!(svg, "<g shape-rendering=\"crispEdges\">");
+    for y in 0..rows {
+        for x in 0..cols {
+            let color = cell_fill(map, x, base + y, options);
+            if color != Color::WHITE && !is_wall(x, y) {
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    num(x as f32 * s), num(y as f32 * s), num(s), num(s), color.to_hex()
+                );
+            }
+        }
+    }
+    let _ = writeln!(svg, "</g>");
+
+    // 墙：长度不小于 2 的水平、竖直连续段各一条连接两端格子中心的线，孤立墙格为一个圆点
+    let mut covered = vec![vec![false; cols as usize]; rows as usize];
+    let mut walls: Vec<((i32, i32), (i32, i32))> = Vec::new();
+    for y in 0..rows {
+        let mut x = 0;
+        while x < cols {
+            let start = x;
+            while x < cols && is_wall(x, y) {
+                x += 1;
+            }
+            if x - start >= 2 {
+                walls.push(((start, y), (x - 1, y)));
+                (start..x).for_each(|cx| covered[y as usize][cx as usize] = true);
+            }
+            x += 1;
+        }
+    }
+    for x in 0..cols {
+        let mut y = 0;
+        while y < rows {
+            let start = y;
+            while y < rows && is_wall(x, y) {
+                y += 1;
+            }
+            if y - start >= 2 {
+                walls.push(((x, start), (x, y - 1)));
+                (start..y).for_each(|cy| covered[cy as usize][x as usize] = true);
+            }
+            y += 1;
+        }
+    }
+    for y in 0..rows {
+        for x in 0..cols {
+            if is_wall(x, y) && !covered[y as usize][x as usize] {
+                walls.push(((x, y), (x, y)));
+            }
+        }
+    }
+    // 圆形线帽使相交的墙在拐角处衔接，长度为 0 的线段显示为圆点
+    let _ = writeln!(
+        svg,
+        r#"<g stroke="{}" stroke-width="{}" stroke-linecap="round">"#,
+        Color::BLACK.to_hex(), num(WALL_WIDTH * s)
+    );
+    for (a, b) in walls {
+        let center = |(x, y): (i32, i32)| ((x as f32 + 0.5) * s, (y as f32 + 0.5) * s);
+        let ((ax, ay), (bx, by)) = (center(a), center(b));
+        let _ = writeln!(svg, r#"<line x1="{}" y1="{}" x2="{}" y2="{}"/>"#, num(ax), num(ay), num(bx), num(by));
+    }
+    let _ = writeln!(svg, "</g>");
+
+    if options.show_path {
+        let center = |(x, y): (i32, i32)| ((x as f32 + 0.5) * s, (y as f32 + 0.5) * s);
+        let segments = path_segments(map, path, options.floor);
+
+        // 首尾相接的段合并为一条折线
+        let mut lines: Vec<Vec<(i32, i32)>> = Vec::new();
+        let mut last: Option<(usize, (i32, i32))> = None;
+        for segment in &segments {
+            match (last, lines.last_mut()) {
+                (Some((index, to)), Some(line)) if index + 1 == segment.index && to == segment.from => line.push(segment.to),
+                _ => lines.push(vec![segment.from, segment.to]),
+            }
+            last = Some((segment.index, segment.to));
+        }
+        let _ = writeln!(
+            svg,
+            r#"<g fill="none" stroke="{}" stroke-width="{}" stroke-dasharray="{} {}">"#,
+            PATH_COLOR.to_hex(), num(0.15 * s), num(0.3 * s), num(0.2 * s)
+        );
+        for line in lines {
+            let points: Vec<String> = line.into_iter()
+                .map(|cell| {
+                    let (px, py) = center(cell);
+                    format!("{},{}", num(px), num(py))
+                })
+                .collect();
+            let _ = writeln!(svg, r#"<polyline points="{}"/>"#, points.join(" "));
+        }
+        let _ = writeln!(svg, "</g>");
+
+        if options.show_arrows {
+            let _ = writeln!(svg, r#"<g fill="{}">"#, PATH_COLOR.to_hex());
+            for segment in segments.iter().filter(|segment| segment.index % 3 == 0) {
+                let (start, end) = (center(segment.from), center(segment.to));
+                let mid = ((start.0 + end.0) / 2.0, (start.1 + end.1) / 2.0);
+                if let Some(points) = arrow_points(mid, segment.direction, 0.3 * s) {
+                    let points: Vec<String> = points.iter().map(|&(px, py)| format!("{},{}", num(px), num(py))).collect();
+                    let _ = writeln!(svg, r#"<polygon points="{}"/>"#, points.join(" "));
+                }
+            }
+            let _ = writeln!(svg, "</g>");
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+/// 将地图及路径导出为 SVG 文件
+pub fn export_svg(
+    map: &Map,
+    path: &[(i32, i32, Direction)],
+    options: &ExportOptions,
+    file: impl AsRef<Path>,
+) -> Result<(), MazeError> {
+    let svg = render_svg(map, path, options)?;
+    std::fs::write(file, svg).map_err(|e| MazeError::Io(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_svg() {
+        let mut map = Map::with_size(10, 10, 100, 100);
+        map.grid[(4, 1)] = CellState::Visited;
+        let path = [(1, 1, Direction::Right), (2, 1, Direction::Right), (3, 1, Direction::None)];
+        let options = ExportOptions { scale: 10, ..Default::default() };
+        let svg = render_svg(&map, &path, &options).unwrap();
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert!(svg.contains(r#"viewBox="0 0 110 110""#));
+        // 顶部外墙合并为一条连接两端格子中心的细线
+        assert!(svg.contains(r#"stroke-width="3" stroke-linecap="round""#));
+        assert!(svg.contains(r#"<line x1="5" y1="5" x2="105" y2="5"/>"#));
+        assert!(svg.contains(r#"<polyline points="15,15 25,15 35,15"/>"#));
+        assert!(svg.contains(r##"fill="#87ceeb""##));
+        assert!(!svg.contains("<rect x=\"0\""));
+
+        let plain = render_svg(&map, &path, &ExportOptions { show_path: false, show_visited: false, ..options }).unwrap();
+        assert!(!plain.contains("<polyline") && !plain.contains("#87ceeb"));
+
+        let result = render_svg(&map, &path, &ExportOptions { floor: 1, ..options });
+        assert_eq!(result.unwrap_err(), MazeError::InvalidFloor { floor: 1, floors: 1 });
+    }
+}
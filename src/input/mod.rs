@@ -2,6 +2,10 @@ use eframe::egui;
 use crate::MazeApp;
 
 pub fn handle_hotkeys(ctx: &egui::Context, app: &mut MazeApp) {
+    // 文本框获得焦点时按键属于输入内容，不作为快捷键
+    if ctx.wants_keyboard_input() {
+        return;
+    }
     if ctx.input(|i| i.key_pressed(egui::Key::Space)) {
         if app.steps.is_empty() {
             app.start_search();
@@ -21,4 +25,10 @@ pub fn handle_hotkeys(ctx: &egui::Context, app: &mut MazeApp) {
     if ctx.input(|i| i.key_pressed(egui::Key::ArrowRight)) {
         app.advance_step();
     }
+    if ctx.input(|i| i.modifiers.command && i.key_pressed(egui::Key::Z)) {
+        app.undo_edit();
+    }
+    if ctx.input(|i| i.modifiers.command && i.key_pressed(egui::Key::Y)) {
+        app.redo_edit();
+    }
 }
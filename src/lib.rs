@@ -5,15 +5,27 @@
 //! - BFS (广度优先搜索)
 //! - DBFS (双向广度优先搜索)
 //! - A* (A-Star 启发式搜索)
+//! - 并行 BFS (基于 rayon 的层同步广度优先搜索)
 
 pub mod core;
 pub mod alg;
+pub mod export;
+pub mod bench;
 
 pub use core::point::{Point, Direction, Color};
-pub use core::map::Map;
+pub use core::map::{Map, Edge, Portal, Topology};
+pub use core::grid::{CellState, Grid};
+pub use core::mask::Mask;
+pub use core::error::MazeError;
+pub use core::raster::ImageImport;
 pub use core::{map, point};
 pub use alg::{Algorithm, DFS, DfsResult, dfs_search};
 pub use alg::{BFS, BfsResult, bfs_search};
 pub use alg::{DBFS, DbfsResult, dbfs_search};
 pub use alg::{AStar, AStarResult, astar_search};
-pub use alg::run_algorithm;
+pub use alg::{ParallelBFS, ParallelBfsResult, parallel_bfs_search};
+pub use alg::{SearchResult, SearchStats, run_algorithm, solve, solve_between, solve_limited};
+pub use alg::{SearchLimits, SearchStatus, try_solve, try_solve_limited};
+pub use alg::{SolveJob, solve_batch, solve_batch_with};
+pub use export::ExportOptions;
+pub use bench::BenchConfig;
@@ -4,13 +4,31 @@ mod config;
 mod render;
 mod ui;
 mod input;
+mod editor;
 
 use eframe::egui;
 use maze_routing_algorithm::*;
-use std::time::Instant;
-use config::{MAP_SIZE, CELL_SIZE};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use config::{MAP_WIDTH, MAP_HEIGHT, CELL_SIZE};
 use render::{RenderState, sync_texture, draw_maze};
 
+/// 迷宫外形（非矩形时通过遮罩生成）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MazeShape {
+    Rect,
+    Circle,
+    Diamond,
+}
+
+/// 在后台线程中进行的搜索
+pub struct PendingSearch {
+    handle: JoinHandle<SearchResult>,
+    cancel: Arc<AtomicBool>,
+}
+
 /// 应用程序状态
 pub struct MazeApp {
     pub map: Map,
@@ -21,6 +39,10 @@ pub struct MazeApp {
     pub steps: Vec<Point>,
     pub found: bool,
     pub path_length: i32,
+    pub stats: SearchStats, // 最近一次搜索的统计
+    pub status: Option<SearchStatus>, // 最近一次搜索的结束原因
+    pub search: Option<PendingSearch>, // 后台进行中的搜索
+    pub search_error: Option<MazeError>, // 起点或终点无效时无法开始搜索的原因
     pub start_time: Option<Instant>,
     pub elapsed_ms: u128,
     pub speed: f32, // 毫秒每步
@@ -29,7 +51,23 @@ pub struct MazeApp {
     pub steps_per_frame: usize, // 每帧执行的步数
     pub path_points: Vec<(i32, i32, Direction)>, // 最终路径点及方向
     pub show_arrows: bool, // 是否显示路径箭头
+    pub portal_pairs: usize, // 生成地图时放置的传送门对数
+    pub portal_cost: i32, // 传送门代价
+    pub door_count: usize, // 生成地图时放置的单向门数量
+    pub floors: i32, // 生成地图时的楼层数
+    pub topology: Topology, // 生成地图时的网格拓扑
+    pub shape: MazeShape, // 生成地图时的迷宫外形
+    pub map_width: i32, // 生成地图时的宽度（grid 坐标）
+    pub map_height: i32, // 生成地图时的高度（grid 坐标）
     pub render_state: RenderState,
+    pub editor: editor::Editor,
+    pub file_path: String, // 文本迷宫的打开/保存路径
+    pub file_status: String, // 最近一次打开/保存的结果
+    pub image_import: ImageImport, // 图片导入参数
+    pub export_path: String, // 导出文件路径
+    pub export_scale: u32, // 导出时每格像素
+    pub export_visited: bool, // 导出时是否按搜索状态着色
+    pub export_stride: usize, // 导出动画时每帧之间的步数
 }
 
 impl MazeApp {
@@ -60,9 +98,9 @@ impl MazeApp {
         
         cc.egui_ctx.set_fonts(fonts);
         
-        let map = Map::with_size(MAP_SIZE, MAP_SIZE, 
-                                  MAP_SIZE * CELL_SIZE as i32, 
-                                  MAP_SIZE * CELL_SIZE as i32);
+        let map = Map::with_size(MAP_WIDTH, MAP_HEIGHT, 
+                                  MAP_WIDTH * CELL_SIZE as i32, 
+                                  MAP_HEIGHT * CELL_SIZE as i32);
         Self {
             original_map: map.clone(),
             map,
@@ -72,6 +110,10 @@ impl MazeApp {
             steps: Vec::new(),
             found: false,
             path_length: 0,
+            stats: SearchStats::default(),
+            status: None,
+            search: None,
+            search_error: None,
             start_time: None,
             elapsed_ms: 0,
             speed: 10.0,
@@ -80,18 +122,166 @@ impl MazeApp {
             steps_per_frame: 1,
             path_points: Vec::new(),
             show_arrows: true,
+            portal_pairs: 0,
+            portal_cost: 1,
+            door_count: 0,
+            floors: 1,
+            topology: Topology::Square,
+            shape: MazeShape::Rect,
+            map_width: MAP_WIDTH,
+            map_height: MAP_HEIGHT,
             render_state: RenderState::default(),
+            editor: editor::Editor::default(),
+            file_path: "maze.txt".to_owned(),
+            file_status: String::new(),
+            image_import: ImageImport::default(),
+            export_path: "maze.png".to_owned(),
+            export_scale: 10,
+            export_visited: true,
+            export_stride: 10,
         }
     }
     
     pub fn generate_new_map(&mut self) {
-        self.map = Map::with_size(MAP_SIZE, MAP_SIZE,
-                                   MAP_SIZE * CELL_SIZE as i32,
-                                   MAP_SIZE * CELL_SIZE as i32);
+        let (mw, mh) = (self.map_width, self.map_height);
+        let cell = self.render_state.cell_size as i32;
+        let (pw, ph) = (mw * cell, mh * cell);
+        // 六边形单元格数与方形迷宫的逻辑单元格数相当；环面要求偶数宽高
+        let (w, h) = match self.topology {
+            Topology::Hex => (mw / 2, mh / 2),
+            Topology::Torus => (mw / 2 * 2, mh / 2 * 2),
+            _ => (mw, mh),
+        };
+        let mask = match self.shape {
+            _ if self.topology == Topology::Polar => None,
+            MazeShape::Rect => None,
+            MazeShape::Circle => Some(Mask::circle(w, h)),
+            MazeShape::Diamond => Some(Mask::diamond(w, h)),
+        };
+        self.map = match (mask, self.topology) {
+            (Some(mask), topology) => match Map::with_mask(mask, topology, pw, ph) {
+                Ok(map) => map,
+                Err(e) => {
+                    self.file_status = format!("无法生成地图: {}", e);
+                    return;
+                }
+            },
+            (None, Topology::Square) => Map::with_floors(w, h, self.floors, pw, ph),
+            (None, Topology::Torus) => Map::torus(w, h, pw, ph),
+            (None, Topology::Hex) => Map::hex(w, h, pw, ph),
+            // 最小尺寸下也至少保留 2 个环
+            (None, Topology::Polar) => Map::polar((w.min(h) / 4).max(2), pw, ph),
+        };
+        self.render_state.floor = 0;
+        self.map.add_random_portals(self.portal_pairs, self.portal_cost);
+        self.map.add_random_doors(self.door_count);
         self.original_map = self.map.clone();
+        self.editor.clear_history();
         self.reset_state();
         self.render_state.texture_dirty = true;
     }
+
+    /// 从 file_path 打开文本迷宫
+    pub fn open_text_map(&mut self) {
+        let loaded = std::fs::read_to_string(&self.file_path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| Map::from_text(&text, 0, 0).map_err(|e| e.to_string()));
+        self.install_loaded_map(loaded);
+    }
+
+    /// 从 file_path 导入图片（PNG / BMP）迷宫
+    pub fn open_image_map(&mut self) {
+        let loaded = Map::load_image(&self.file_path, &self.image_import).map_err(|e| e.to_string());
+        self.install_loaded_map(loaded);
+    }
+
+    /// 用从文件加载的地图替换当前地图
+    fn install_loaded_map(&mut self, loaded: Result<Map, String>) {
+        let cell = self.render_state.cell_size as i32;
+        match loaded {
+            Ok(mut map) => {
+                (map.width, map.height) = (map.m * cell, map.n * cell);
+                self.file_status = format!("已打开 {}×{}", map.m, map.n);
+                (self.map_width, self.map_height) = (map.m, map.n);
+                self.topology = Topology::Square;
+                self.map = map;
+                self.original_map = self.map.clone();
+                self.render_state.floor = 0;
+                self.editor.clear_history();
+                self.reset_state();
+            }
+            Err(e) => self.file_status = format!("打开失败: {}", e),
+        }
+    }
+
+    /// 将原始地图以文本格式保存到 file_path
+    pub fn save_text_map(&mut self) {
+        self.file_status = match std::fs::write(&self.file_path, self.original_map.to_text()) {
+            Ok(()) => "已保存".to_owned(),
+            Err(e) => format!("保存失败: {}", e),
+        };
+    }
+
+    /// 当前地图的导出参数（仅在动画结束后叠加路径）
+    fn export_options(&self) -> ExportOptions {
+        ExportOptions {
+            scale: self.export_scale,
+            show_path: self.show_arrows && self.step_index >= self.steps.len(),
+            show_arrows: true,
+            show_visited: self.export_visited,
+            floor: self.render_state.floor,
+        }
+    }
+
+    /// 将当前地图（含搜索状态和路径）导出为 PNG 或 SVG，扩展名按格式替换
+    pub fn export_map(&mut self, svg: bool) {
+        let options = self.export_options();
+        let file = std::path::Path::new(&self.export_path).with_extension(if svg { "svg" } else { "png" });
+        let result = if svg {
+            export::export_svg(&self.map, &self.path_points, &options, &file)
+        } else {
+            export::export_png(&self.map, &self.path_points, &options, &file)
+        };
+        self.file_status = match result {
+            Ok(()) => format!("已导出 {}", file.display()),
+            Err(e) => format!("导出失败: {}", e),
+        };
+    }
+
+    /// 将整个搜索过程导出为 GIF 动画，或导出到 `<文件名>_frames` 目录下的 PNG 帧序列
+    pub fn export_animation(&mut self, gif: bool) {
+        let options = ExportOptions { show_path: self.show_arrows, ..self.export_options() };
+        let animation = export::AnimationOptions { stride: self.export_stride, ..Default::default() };
+        let path = std::path::Path::new(&self.export_path);
+        let (target, result) = if gif {
+            let file = path.with_extension("gif");
+            let result = export::export_gif(&self.original_map, &self.steps, &options, &animation, &file);
+            (file, result)
+        } else {
+            let stem = path.file_stem().map_or("maze".into(), |s| s.to_string_lossy());
+            let dir = path.with_file_name(format!("{}_frames", stem));
+            let result = export::export_frames(&self.original_map, &self.steps, &options, &animation, &dir);
+            (dir, result)
+        };
+        self.file_status = match result {
+            Ok(frames) => format!("已导出 {}（{} 帧）", target.display(), frames),
+            Err(e) => format!("导出失败: {}", e),
+        };
+    }
+
+    /// 撤销编辑器中的上一次修改
+    pub fn undo_edit(&mut self) {
+        if self.editor.undo(&mut self.original_map) {
+            self.reset_map();
+        }
+    }
+
+    /// 重做编辑器中被撤销的修改
+    pub fn redo_edit(&mut self) {
+        if self.editor.redo(&mut self.original_map) {
+            self.reset_map();
+        }
+    }
     
     pub fn reset_map(&mut self) {
         self.map = self.original_map.clone();
@@ -105,6 +295,13 @@ impl MazeApp {
         self.steps.clear();
         self.found = false;
         self.path_length = 0;
+        self.stats = SearchStats::default();
+        self.status = None;
+        self.search_error = None;
+        // 丢弃进行中的搜索，后台线程会在下一次检查取消标志时结束
+        if let Some(search) = self.search.take() {
+            search.cancel.store(true, Ordering::Relaxed);
+        }
         self.start_time = None;
         self.elapsed_ms = 0;
         self.last_update = Instant::now();
@@ -113,21 +310,50 @@ impl MazeApp {
     }
     
     pub fn start_search(&mut self) {
+        if self.search.is_some() {
+            return;
+        }
         self.map = self.original_map.clone();
+        self.reset_state();
+        if let Err(e) = self.map.check_endpoints(self.map.start(), self.map.end()) {
+            self.search_error = Some(e);
+            return;
+        }
+        
+        // 在后台线程中预计算所有步骤，大地图上搜索时界面仍可响应并中止
+        let cancel = Arc::new(AtomicBool::new(false));
+        let limits = SearchLimits::default().with_cancel(cancel.clone());
+        let (mut map, algorithm) = (self.map.clone(), self.algorithm);
+        let handle = std::thread::spawn(move || {
+            let (start, goal) = (map.start(), map.end());
+            solve_limited(&mut map, algorithm, start, goal, &limits)
+        });
+        self.search = Some(PendingSearch { handle, cancel });
+    }
+    
+    /// 中止后台搜索，已搜索的部分仍会回放
+    pub fn cancel_search(&mut self) {
+        if let Some(search) = &self.search {
+            search.cancel.store(true, Ordering::Relaxed);
+        }
+    }
+    
+    /// 后台搜索结束后取回结果并开始回放
+    fn poll_search(&mut self) {
+        if !self.search.as_ref().is_some_and(|s| s.handle.is_finished()) {
+            return;
+        }
+        let Some(Ok(result)) = self.search.take().map(|s| s.handle.join()) else {
+            return;
+        };
+        self.steps = result.steps;
+        self.found = result.found;
+        self.path_length = result.path_length;
+        self.stats = result.stats;
+        self.status = Some(result.status);
         self.running = true;
-        self.step_index = 0;
-        self.found = false;
-        self.path_length = 0;
         self.start_time = Some(Instant::now());
         self.last_update = Instant::now();
-        self.render_state.texture_dirty = true;
-        
-        // 预计算所有步骤
-        let mut temp_map = self.map.clone();
-        let (steps, found, path_len) = run_algorithm(&mut temp_map, self.algorithm);
-        self.steps = steps;
-        self.found = found;
-        self.path_length = path_len;
         
         // 提取最终路径点及方向
         self.extract_path_directions();
@@ -135,49 +361,7 @@ impl MazeApp {
     
     /// 从步骤中提取最终路径及方向
     fn extract_path_directions(&mut self) {
-        self.path_points.clear();
-        
-        // 收集所有 LIGHT_RED 颜色的点（回溯路径）或 AQUAMARINE 颜色的点（最终路径）
-        let mut path_steps: Vec<_> = self.steps.iter()
-            .filter(|s| s.color == Color::LIGHT_RED || s.color == Color::AQUAMARINE)
-            .collect();
-        
-        if path_steps.is_empty() {
-            return;
-        }
-        
-        // 如果是 LIGHT_RED（回溯顺序是从终点到起点），需要反转
-        // 检查第一个点的颜色判断
-        if !path_steps.is_empty() && path_steps[0].color == Color::LIGHT_RED {
-            path_steps.reverse();
-        }
-        
-        // 去重（可能有重复的点）
-        let mut seen = std::collections::HashSet::new();
-        let unique_steps: Vec<_> = path_steps.into_iter()
-            .filter(|s| seen.insert((s.x, s.y)))
-            .collect();
-        
-        // 计算每个点到下一个点的方向
-        for i in 0..unique_steps.len() {
-            let current = unique_steps[i];
-            let direction = if i + 1 < unique_steps.len() {
-                let next = unique_steps[i + 1];
-                let dx = next.x - current.x;
-                let dy = next.y - current.y;
-                match (dx, dy) {
-                    (1, 0) => Direction::Right,
-                    (-1, 0) => Direction::Left,
-                    (0, 1) => Direction::Down,
-                    (0, -1) => Direction::Up,
-                    _ => Direction::None,
-                }
-            } else {
-                Direction::None // 终点没有方向
-            };
-            
-            self.path_points.push((current.x, current.y, direction));
-        }
+        self.path_points = self.map.trace_path(&self.steps);
     }
     
     fn update(&mut self) {
@@ -200,18 +384,7 @@ impl MazeApp {
     /// 快速步进，不更新时间（用于批量执行）
     #[inline(always)]
     fn advance_step_fast(&mut self) {
-        let step = &self.steps[self.step_index];
-        
-        // 使用 match 替代多个 if-else，编译器可更好优化
-        let cell_value = match step.color {
-            Color::YELLOW => 4,
-            Color::SKY_BLUE => 2,
-            Color::LIGHT_RED => 3,
-            Color::AQUAMARINE => 5,
-            _ => self.map.get_cell(step.x, step.y),
-        };
-        
-        self.map.set_cell(step.x, step.y, cell_value);
+        self.map.apply_step(&self.steps[self.step_index]);
         self.step_index += 1;
         self.render_state.texture_dirty = true;
         
@@ -238,20 +411,8 @@ impl MazeApp {
             self.step_index -= 1;
             // 重新应用所有步骤到当前索引
             self.map = self.original_map.clone();
-            for i in 0..self.step_index {
-                let step = &self.steps[i];
-                let cell_value = if step.color == Color::YELLOW {
-                    4
-                } else if step.color == Color::SKY_BLUE {
-                    2
-                } else if step.color == Color::LIGHT_RED {
-                    3
-                } else if step.color == Color::AQUAMARINE {
-                    5
-                } else {
-                    self.map.get_cell(step.x, step.y)
-                };
-                self.map.set_cell(step.x, step.y, cell_value);
+            for step in &self.steps[..self.step_index] {
+                self.map.apply_step(step);
             }
             self.render_state.texture_dirty = true;
         }
@@ -262,11 +423,14 @@ impl MazeApp {
 impl eframe::App for MazeApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // 更新搜索步骤
+        self.poll_search();
         self.update();
         
-        // 如果正在运行，持续请求重绘
+        // 如果正在运行，持续请求重绘；后台搜索期间定时检查是否完成
         if self.running {
             ctx.request_repaint();
+        } else if self.search.is_some() {
+            ctx.request_repaint_after(Duration::from_millis(50));
         }
         
         ui::top_bar(ctx);
@@ -274,16 +438,38 @@ impl eframe::App for MazeApp {
         ui::right_panel(ctx, self);
         
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.vertical_centered(|ui| {
-                ui.add_space(10.0);
-                sync_texture(&mut self.render_state, ctx, &self.map);
-                draw_maze(
-                    &self.render_state,
-                    ui,
-                    &self.path_points,
-                    self.step_index >= self.steps.len(),
-                    self.show_arrows,
-                );
+            // 大地图超出窗口时可滚动查看
+            egui::ScrollArea::both().show(ui, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(10.0);
+                    let steps_finished = self.step_index >= self.steps.len();
+                    match self.map.topology {
+                        Topology::Square | Topology::Torus => {
+                            sync_texture(&mut self.render_state, ctx, &self.map);
+                            let response = draw_maze(
+                                &self.render_state,
+                                ui,
+                                &self.map,
+                                &self.path_points,
+                                steps_finished,
+                                self.show_arrows,
+                            );
+                            // 编辑模式下修改原始地图，并清除已有的搜索结果
+                            if self.editor.enabled {
+                                let (cell_size, floor) = (self.render_state.cell_size, self.render_state.floor);
+                                if self.editor.handle(ui, &response, &mut self.original_map, cell_size, floor) {
+                                    self.reset_map();
+                                }
+                            }
+                        }
+                        Topology::Hex => {
+                            render::hex::draw_hex_maze(ui, &self.map, &self.path_points, steps_finished, self.show_arrows, self.render_state.cell_size);
+                        }
+                        Topology::Polar => {
+                            render::polar::draw_polar_maze(ui, &self.map, &self.path_points, steps_finished, self.show_arrows, self.render_state.cell_size);
+                        }
+                    }
+                });
             });
         });
 
@@ -0,0 +1,94 @@
+use eframe::egui;
+use crate::{config::{CELL_COLORS, PORTAL_COLOR, DOOR_COLOR}, Map, Direction};
+use super::draw_arrow;
+use maze_routing_algorithm::core::hex::hex_directions;
+
+/// 六边形网格的几何参数
+struct HexLayout {
+    origin: egui::Pos2,
+    radius: f32,
+}
+
+impl HexLayout {
+    /// 根据画布大小计算六边形外接圆半径
+    fn fit(origin: egui::Pos2, size: egui::Vec2, map: &Map) -> Self {
+        let sqrt3 = 3f32.sqrt();
+        let by_width = size.x / ((map.m as f32 + 0.5) * sqrt3);
+        let by_height = size.y / (1.5 * map.n as f32 + 0.5);
+        Self { origin, radius: by_width.min(by_height) }
+    }
+
+    /// 单元格中心（偶数行向右错开半格）
+    fn center(&self, x: i32, y: i32) -> egui::Pos2 {
+        let w = 3f32.sqrt() * self.radius;
+        let shift = if y % 2 == 0 { 0.5 } else { 0.0 };
+        egui::pos2(
+            self.origin.x + (x as f32 - 1.0 + shift) * w + w / 2.0,
+            self.origin.y + (y as f32 - 1.0) * 1.5 * self.radius + self.radius,
+        )
+    }
+
+    /// 第 k 个顶点，顶点 k 与 k + 1 之间的边对应 hex_directions 中的第 k 个方向
+    fn corner(&self, center: egui::Pos2, k: usize) -> egui::Pos2 {
+        let angle = (60.0 * k as f32 - 30.0).to_radians();
+        egui::pos2(center.x + self.radius * angle.cos(), center.y + self.radius * angle.sin())
+    }
+}
+
+/// 绘制六边形迷宫：单元格状态填色、未打通的墙、传送门连线、单向门和最终路径
+pub fn draw_hex_maze(
+    ui: &mut egui::Ui,
+    map: &Map,
+    path_points: &[(i32, i32, Direction)],
+    steps_finished: bool,
+    show_arrows: bool,
+    cell_size: f32,
+) {
+    let size = super::canvas_size(map, cell_size);
+    let (response, painter) = ui.allocate_painter(size, egui::Sense::hover());
+    let layout = HexLayout::fit(response.rect.min, size, map);
+    let wall = egui::Stroke::new(1.5, CELL_COLORS[1]);
+
+    for y in 1..=map.n {
+        for x in 1..=map.m {
+            if map.is_void(x, y) {
+                continue;
+            }
+            let center = layout.center(x, y);
+            let corners: Vec<egui::Pos2> = (0..6).map(|k| layout.corner(center, k)).collect();
+            let v = map.get_cell(x, y) as usize;
+            let fill = CELL_COLORS[if v < CELL_COLORS.len() { v } else { 0 }];
+            painter.add(egui::Shape::convex_polygon(corners.clone(), fill, egui::Stroke::NONE));
+
+            for (k, (dx, dy, _)) in hex_directions(y).iter().enumerate() {
+                let (nx, ny) = (x + dx, y + dy);
+                if !map.in_bounds(nx, ny) || !map.has_passage((x, y), (nx, ny)) {
+                    painter.line_segment([corners[k], corners[(k + 1) % 6]], wall);
+                }
+            }
+        }
+    }
+
+    let portal_stroke = egui::Stroke::new(1.0, PORTAL_COLOR);
+    for portal in &map.portals {
+        let a = layout.center(portal.a.0, portal.a.1);
+        let b = layout.center(portal.b.0, portal.b.1);
+        painter.line_segment([a, b], egui::Stroke::new(1.0, PORTAL_COLOR.gamma_multiply(0.5)));
+        painter.circle_stroke(a, layout.radius * 0.6, portal_stroke);
+        painter.circle_stroke(b, layout.radius * 0.6, portal_stroke);
+    }
+
+    for (&(x, y), &direction) in &map.doors {
+        draw_arrow(&painter, layout.center(x, y), direction, layout.radius * 0.5, DOOR_COLOR);
+    }
+
+    if show_arrows && steps_finished && path_points.len() > 1 {
+        let stroke = egui::Stroke::new(1.5, egui::Color32::from_rgb(139, 69, 19));
+        for pair in path_points.windows(2) {
+            let ((x1, y1, dir), (x2, y2, _)) = (pair[0], pair[1]);
+            if dir != Direction::Portal {
+                painter.line_segment([layout.center(x1, y1), layout.center(x2, y2)], stroke);
+            }
+        }
+    }
+}
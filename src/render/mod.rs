@@ -1,53 +1,68 @@
+pub mod hex;
+pub mod polar;
+
 use eframe::egui;
-use crate::{config::{MAP_SIZE, CELL_SIZE, CELL_COLORS}, Map, Direction};
+use crate::{config::{CELL_SIZE, CELL_COLORS, PORTAL_COLOR, DOOR_COLOR, STAIR_COLOR, VOID_COLOR}, Map, Direction, Topology};
 
 pub struct RenderState {
     pub texture: Option<egui::TextureHandle>,
     pub texture_dirty: bool,
+    pub floor: i32, // 当前显示的楼层
+    pub cell_size: f32, // 单元格像素大小
 }
 
 impl Default for RenderState {
     fn default() -> Self {
-        Self { texture: None, texture_dirty: true }
+        Self { texture: None, texture_dirty: true, floor: 0, cell_size: CELL_SIZE }
     }
 }
 
-/// 将当前地图同步到 GPU 纹理
+/// 按地图实际尺寸计算画布大小（六边形 / 极坐标的一个单元格约占方形网格的 2 / 4 格）
+pub fn canvas_size(map: &Map, cell_size: f32) -> egui::Vec2 {
+    let (cols, rows) = match map.topology {
+        Topology::Square | Topology::Torus => (map.m + 1, map.n + 1),
+        Topology::Hex => (map.m * 2 + 1, map.n * 2 + 1),
+        Topology::Polar => (map.n * 4 + 1, map.n * 4 + 1),
+    };
+    egui::vec2(cols as f32 * cell_size, rows as f32 * cell_size)
+}
+
+/// 将当前地图（当前楼层）同步到 GPU 纹理，地图尺寸变化时重新分配纹理
 pub fn sync_texture(state: &mut RenderState, ctx: &egui::Context, map: &Map) {
     if !state.texture_dirty {
         return;
     }
-    let w = (MAP_SIZE + 1) as usize;
-    let h = (MAP_SIZE + 1) as usize;
+    let w = (map.m + 1) as usize;
+    let h = (map.n + 1) as usize;
+    let base = map.floor_offset(state.floor) as usize;
     let mut image = egui::ColorImage::new([w, h], egui::Color32::BLACK);
     for y in 0..h {
         for x in 0..w {
-            let v = map.grid[y][x] as usize;
-            let idx = if v < CELL_COLORS.len() { v } else { 0 };
-            image[(x, y)] = CELL_COLORS[idx];
+            // CELL_COLORS 按 CellState 的数值排列
+            let state = map.grid[(x, base + y)];
+            image[(x, y)] = if map.is_void(x as i32, (base + y) as i32) { VOID_COLOR } else { CELL_COLORS[state as usize] };
         }
     }
     let options = egui::TextureOptions::NEAREST;
-    if let Some(tex) = state.texture.as_mut() {
-        tex.set(image, options);
-    } else {
-        state.texture = Some(ctx.load_texture("maze_grid_texture", image, options));
+    match state.texture.as_mut() {
+        Some(tex) if tex.size() == [w, h] => tex.set(image, options),
+        _ => state.texture = Some(ctx.load_texture("maze_grid_texture", image, options)),
     }
     state.texture_dirty = false;
 }
 
-/// 绘制地图纹理和路径箭头
+/// 绘制地图纹理（当前楼层）、传送门连线、单向门、楼梯和路径箭头，返回画布的交互响应
 pub fn draw_maze(
     state: &RenderState,
     ui: &mut egui::Ui,
+    map: &Map,
     path_points: &[(i32, i32, Direction)],
     steps_finished: bool,
     show_arrows: bool,
-) {
-    let (response, painter) = ui.allocate_painter(
-        egui::vec2((MAP_SIZE + 1) as f32 * CELL_SIZE, (MAP_SIZE + 1) as f32 * CELL_SIZE),
-        egui::Sense::hover()
-    );
+) -> egui::Response {
+    let cell_size = state.cell_size;
+    let size = canvas_size(map, cell_size);
+    let (response, painter) = ui.allocate_painter(size, egui::Sense::click_and_drag());
 
     let rect = response.rect;
     let base_x = rect.min.x;
@@ -55,10 +70,7 @@ pub fn draw_maze(
 
     if let Some(tex) = state.texture.as_ref() {
         let tex_id = tex.id();
-        let rect = egui::Rect::from_min_size(
-            egui::pos2(base_x, base_y),
-            egui::vec2((MAP_SIZE + 1) as f32 * CELL_SIZE, (MAP_SIZE + 1) as f32 * CELL_SIZE)
-        );
+        let rect = egui::Rect::from_min_size(egui::pos2(base_x, base_y), size);
         painter.image(
             tex_id,
             rect,
@@ -67,22 +79,93 @@ pub fn draw_maze(
         );
     }
 
+    // 仅绘制当前楼层上的元素，坐标换算为层内坐标
+    let floor = state.floor;
+    let on_floor = |y: i32| map.floor_of(y) == floor;
+    let cell_center = |x: i32, y: i32| egui::pos2(
+        base_x + (x as f32 + 0.5) * cell_size,
+        base_y + ((y - map.floor_offset(floor)) as f32 + 0.5) * cell_size
+    );
+
+    // 绘制传送门连线（两端不在同一层时只标记本层端点）
+    let portal_stroke = egui::Stroke::new(1.0, PORTAL_COLOR);
+    for portal in &map.portals {
+        let (a_here, b_here) = (on_floor(portal.a.1), on_floor(portal.b.1));
+        let a = cell_center(portal.a.0, portal.a.1);
+        let b = cell_center(portal.b.0, portal.b.1);
+        if a_here && b_here {
+            painter.line_segment([a, b], egui::Stroke::new(1.0, PORTAL_COLOR.gamma_multiply(0.5)));
+        }
+        if a_here {
+            painter.circle_stroke(a, cell_size * 0.4, portal_stroke);
+        }
+        if b_here {
+            painter.circle_stroke(b, cell_size * 0.4, portal_stroke);
+        }
+    }
+
+    // 标注地形权重（格子过小时省略）
+    if cell_size >= 8.0 {
+        for (&(x, y), &weight) in &map.weights {
+            if on_floor(y) {
+                painter.text(
+                    cell_center(x, y),
+                    egui::Align2::CENTER_CENTER,
+                    weight.to_string(),
+                    egui::FontId::monospace(cell_size * 0.8),
+                    egui::Color32::DARK_GRAY,
+                );
+            }
+        }
+    }
+
+    // 绘制单向门方向
+    for (&(x, y), &direction) in &map.doors {
+        if on_floor(y) {
+            draw_arrow(&painter, cell_center(x, y), direction, cell_size * 0.4, DOOR_COLOR);
+        }
+    }
+
+    // 绘制楼梯：上行楼梯在下端标记，下行楼梯在上端标记
+    for &(x, y) in &map.stairs {
+        let upper = y + map.n + 1;
+        if on_floor(y) {
+            draw_arrow(&painter, cell_center(x, y), Direction::Up, cell_size * 0.3, STAIR_COLOR);
+        }
+        if on_floor(upper) {
+            draw_arrow(&painter, cell_center(x, upper), Direction::Down, cell_size * 0.3, STAIR_COLOR);
+        }
+    }
+
+    // 高亮路径换层的位置
+    if steps_finished {
+        for &(x, y, direction) in path_points {
+            let changes_level = matches!(direction, Direction::Ascend | Direction::Descend);
+            if changes_level && on_floor(y) {
+                painter.circle_stroke(cell_center(x, y), cell_size * 0.7, egui::Stroke::new(2.0, STAIR_COLOR));
+            }
+        }
+    }
+
     // 绘制路径方向虚线箭头
     if show_arrows && steps_finished && path_points.len() > 1 {
         let line_color = egui::Color32::from_rgb(139, 69, 19);
         let stroke = egui::Stroke::new(1.5, line_color);
         for i in 0..path_points.len() - 1 {
-            let (x1, y1, _) = path_points[i];
+            let (x1, y1, dir) = path_points[i];
             let (x2, y2, _) = path_points[i + 1];
 
-            let start = egui::pos2(
-                base_x + (x1 as f32 + 0.5) * CELL_SIZE,
-                base_y + (y1 as f32 + 0.5) * CELL_SIZE
-            );
-            let end = egui::pos2(
-                base_x + (x2 as f32 + 0.5) * CELL_SIZE,
-                base_y + (y2 as f32 + 0.5) * CELL_SIZE
-            );
+            // 传送门跳转由连线表示、换层由高亮表示，不绘制虚线
+            if matches!(dir, Direction::Portal | Direction::Ascend | Direction::Descend) {
+                continue;
+            }
+            // 跨越环面接缝的一步不绘制横穿整个画布的虚线
+            if !on_floor(y1) || !on_floor(y2) || (x2 - x1).abs() + (y2 - y1).abs() != 1 {
+                continue;
+            }
+
+            let start = cell_center(x1, y1);
+            let end = cell_center(x2, y2);
 
             let dx = end.x - start.x;
             let dy = end.y - start.y;
@@ -106,29 +189,38 @@ pub fn draw_maze(
             if i % 3 == 0 {
                 let (_, _, direction) = path_points[i];
                 let mid = egui::pos2((start.x + end.x) / 2.0, (start.y + end.y) / 2.0);
-                let arrow_size = 3.0;
-                let (arrow_dx, arrow_dy): (f32, f32) = match direction {
-                    Direction::Up => (0.0, -1.0),
-                    Direction::Down => (0.0, 1.0),
-                    Direction::Left => (-1.0, 0.0),
-                    Direction::Right => (1.0, 0.0),
-                    Direction::None => (0.0, 0.0),
-                };
-
-                if arrow_dx != 0.0 || arrow_dy != 0.0 {
-                    let tip = egui::pos2(mid.x + arrow_dx * arrow_size, mid.y + arrow_dy * arrow_size);
-                    let left = egui::pos2(
-                        mid.x - arrow_dy * arrow_size * 0.5 - arrow_dx * arrow_size,
-                        mid.y + arrow_dx * arrow_size * 0.5 - arrow_dy * arrow_size,
-                    );
-                    let right = egui::pos2(
-                        mid.x + arrow_dy * arrow_size * 0.5 - arrow_dx * arrow_size,
-                        mid.y - arrow_dx * arrow_size * 0.5 - arrow_dy * arrow_size,
-                    );
-
-                    painter.add(egui::Shape::convex_polygon(vec![tip, left, right], line_color, egui::Stroke::NONE));
-                }
+                draw_arrow(&painter, mid, direction, 3.0, line_color);
             }
         }
     }
+
+    response
+}
+
+/// 在 center 处绘制指向 direction 的三角箭头
+pub fn draw_arrow(painter: &egui::Painter, center: egui::Pos2, direction: Direction, size: f32, color: egui::Color32) {
+    let (arrow_dx, arrow_dy): (f32, f32) = match direction {
+        Direction::Up => (0.0, -1.0),
+        Direction::Down => (0.0, 1.0),
+        Direction::Left => (-1.0, 0.0),
+        Direction::Right => (1.0, 0.0),
+        // 斜向仅用于六边形网格，取六边形边的法线方向
+        Direction::UpLeft => (-0.5, -0.866),
+        Direction::UpRight => (0.5, -0.866),
+        Direction::DownLeft => (-0.5, 0.866),
+        Direction::DownRight => (0.5, 0.866),
+        Direction::None | Direction::Portal | Direction::Ascend | Direction::Descend => return,
+    };
+
+    let tip = egui::pos2(center.x + arrow_dx * size, center.y + arrow_dy * size);
+    let left = egui::pos2(
+        center.x - arrow_dy * size * 0.5 - arrow_dx * size,
+        center.y + arrow_dx * size * 0.5 - arrow_dy * size,
+    );
+    let right = egui::pos2(
+        center.x + arrow_dy * size * 0.5 - arrow_dx * size,
+        center.y - arrow_dx * size * 0.5 - arrow_dy * size,
+    );
+
+    painter.add(egui::Shape::convex_polygon(vec![tip, left, right], color, egui::Stroke::NONE));
 }
@@ -0,0 +1,131 @@
+use std::f32::consts::TAU;
+use eframe::egui;
+use crate::{config::{CELL_COLORS, PORTAL_COLOR, DOOR_COLOR}, Map, Direction};
+
+/// 圆形网格的几何参数
+struct PolarLayout {
+    center: egui::Pos2,
+    ring_height: f32,
+}
+
+impl PolarLayout {
+    /// 第 y 环的内外半径
+    fn radii(&self, y: i32) -> (f32, f32) {
+        ((y - 1) as f32 * self.ring_height, y as f32 * self.ring_height)
+    }
+
+    /// 第 y 环第 x 个单元格的起止角度（从正上方开始顺时针）
+    fn angles(&self, map: &Map, x: i32, y: i32) -> (f32, f32) {
+        let step = TAU / map.ring_cells(y) as f32;
+        ((x - 1) as f32 * step, x as f32 * step)
+    }
+
+    fn point(&self, radius: f32, angle: f32) -> egui::Pos2 {
+        let a = angle - TAU / 4.0;
+        egui::pos2(self.center.x + radius * a.cos(), self.center.y + radius * a.sin())
+    }
+
+    /// 沿圆弧采样的折线点
+    fn arc(&self, radius: f32, from: f32, to: f32) -> Vec<egui::Pos2> {
+        let segments = ((to - from) / 0.1).ceil().max(1.0) as usize;
+        (0..=segments)
+            .map(|i| self.point(radius, from + (to - from) * i as f32 / segments as f32))
+            .collect()
+    }
+
+    /// 单元格中心（圆心单元格取圆心）
+    fn cell_center(&self, map: &Map, x: i32, y: i32) -> egui::Pos2 {
+        if y == 1 {
+            return self.center;
+        }
+        let (r0, r1) = self.radii(y);
+        let (a0, a1) = self.angles(map, x, y);
+        self.point((r0 + r1) / 2.0, (a0 + a1) / 2.0)
+    }
+}
+
+/// 绘制圆形迷宫：单元格状态填色、环弧与径向墙、传送门连线、单向门和最终路径
+pub fn draw_polar_maze(
+    ui: &mut egui::Ui,
+    map: &Map,
+    path_points: &[(i32, i32, Direction)],
+    steps_finished: bool,
+    show_arrows: bool,
+    cell_size: f32,
+) {
+    let size = super::canvas_size(map, cell_size);
+    let (response, painter) = ui.allocate_painter(size, egui::Sense::hover());
+    let layout = PolarLayout {
+        center: response.rect.center(),
+        ring_height: (size.x.min(size.y) / 2.0 - 2.0) / map.n as f32,
+    };
+    let wall = egui::Stroke::new(1.5, CELL_COLORS[1]);
+    let cells = map.cell_coords();
+
+    // 以三角网格填充每个环形扇区
+    let mut mesh = egui::Mesh::default();
+    for &(x, y) in &cells {
+        let v = map.get_cell(x, y) as usize;
+        let color = CELL_COLORS[if v < CELL_COLORS.len() { v } else { 0 }];
+        let (r0, r1) = layout.radii(y);
+        let (a0, a1) = if y == 1 { (0.0, TAU) } else { layout.angles(map, x, y) };
+        let inner = layout.arc(r0, a0, a1);
+        let outer = layout.arc(r1, a0, a1);
+        let base = mesh.vertices.len() as u32;
+        for (p, q) in inner.iter().zip(outer.iter()) {
+            mesh.colored_vertex(*p, color);
+            mesh.colored_vertex(*q, color);
+        }
+        for i in 0..(inner.len() as u32 - 1) {
+            let k = base + i * 2;
+            mesh.add_triangle(k, k + 1, k + 3);
+            mesh.add_triangle(k, k + 3, k + 2);
+        }
+    }
+    painter.add(egui::Shape::mesh(mesh));
+
+    // 墙：每个单元格负责顺时针一侧的径向墙和外侧的环弧
+    for &(x, y) in &cells {
+        let (_, r1) = layout.radii(y);
+        let count = map.ring_cells(y);
+        let (_, a1) = layout.angles(map, x, y);
+        if count > 1 && !map.has_passage((x, y), (x % count + 1, y)) {
+            let (r0, _) = layout.radii(y);
+            painter.line_segment([layout.point(r0, a1), layout.point(r1, a1)], wall);
+        }
+        let children = map.polar_children(x, y);
+        if children.is_empty() {
+            let (a0, a1) = if y == 1 { (0.0, TAU) } else { layout.angles(map, x, y) };
+            painter.add(egui::Shape::line(layout.arc(r1, a0, a1), wall));
+        }
+        for (cx, cy) in children {
+            if !map.has_passage((x, y), (cx, cy)) {
+                let (a0, a1) = layout.angles(map, cx, cy);
+                painter.add(egui::Shape::line(layout.arc(r1, a0, a1), wall));
+            }
+        }
+    }
+
+    let portal_stroke = egui::Stroke::new(1.0, PORTAL_COLOR);
+    for portal in &map.portals {
+        let a = layout.cell_center(map, portal.a.0, portal.a.1);
+        let b = layout.cell_center(map, portal.b.0, portal.b.1);
+        painter.line_segment([a, b], egui::Stroke::new(1.0, PORTAL_COLOR.gamma_multiply(0.5)));
+        painter.circle_stroke(a, layout.ring_height * 0.3, portal_stroke);
+        painter.circle_stroke(b, layout.ring_height * 0.3, portal_stroke);
+    }
+
+    for &(x, y) in map.doors.keys() {
+        painter.circle_filled(layout.cell_center(map, x, y), layout.ring_height * 0.2, DOOR_COLOR);
+    }
+
+    if show_arrows && steps_finished && path_points.len() > 1 {
+        let stroke = egui::Stroke::new(1.5, egui::Color32::from_rgb(139, 69, 19));
+        for pair in path_points.windows(2) {
+            let ((x1, y1, dir), (x2, y2, _)) = (pair[0], pair[1]);
+            if dir != Direction::Portal {
+                painter.line_segment([layout.cell_center(map, x1, y1), layout.cell_center(map, x2, y2)], stroke);
+            }
+        }
+    }
+}
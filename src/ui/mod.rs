@@ -1,4 +1,4 @@
-use crate::{Algorithm, MazeApp, config::{MAP_SIZE, CELL_SIZE}};
+use crate::{Algorithm, MazeApp, SearchStatus, MazeShape, Topology, render::canvas_size, config::{MAP_DIM_RANGE, CELL_SIZE_RANGE}};
 use eframe::egui;
 
 pub fn top_bar(ctx: &egui::Context) {
@@ -9,7 +9,7 @@ pub fn top_bar(ctx: &egui::Context) {
 }
 
 pub fn left_panel(ctx: &egui::Context, app: &mut MazeApp) {
-    let canvas_height = (MAP_SIZE + 1) as f32 * CELL_SIZE;
+    let canvas_height = canvas_size(&app.map, app.render_state.cell_size).y;
     egui::SidePanel::left("left_panel").min_width(230.0).show(ctx, |ui| {
         ui.set_max_height(canvas_height);
         egui::ScrollArea::vertical().max_height(canvas_height).show(ui, |ui| {
@@ -30,13 +30,20 @@ pub fn left_panel(ctx: &egui::Context, app: &mut MazeApp) {
                 if ui.add(egui::RadioButton::new(app.algorithm == Algorithm::AStar, "⭐ A* (启发式)")).clicked() {
                     app.algorithm = Algorithm::AStar;
                 }
+                if ui.add(egui::RadioButton::new(app.algorithm == Algorithm::ParallelBFS, "⚡ 并行 BFS (层同步)")).clicked() {
+                    app.algorithm = Algorithm::ParallelBFS;
+                }
             });
 
             ui.separator();
 
             ui.group(|ui| {
                 ui.label("操作:");
-                if ui.button("▶ 开始搜索").clicked() {
+                if app.search.is_some() {
+                    if ui.button("⏹ 中止搜索").clicked() {
+                        app.cancel_search();
+                    }
+                } else if ui.button("▶ 开始搜索").clicked() {
                     app.start_search();
                 }
                 if ui.button("⏸ 暂停/继续").clicked() {
@@ -76,12 +83,147 @@ pub fn left_panel(ctx: &egui::Context, app: &mut MazeApp) {
                 ui.separator();
                 ui.checkbox(&mut app.show_arrows, "🔀 显示路径箭头");
             });
+
+            ui.separator();
+
+            ui.group(|ui| {
+                ui.label("编辑:");
+                let editable = matches!(app.map.topology, Topology::Square | Topology::Torus);
+                ui.add_enabled_ui(editable, |ui| {
+                    ui.checkbox(&mut app.editor.enabled, "✏ 编辑模式");
+                    ui.label("左键画墙 / 右键擦除 / 拖动 S、G");
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(app.editor.can_undo(), egui::Button::new("↶ 撤销")).clicked() {
+                            app.undo_edit();
+                        }
+                        if ui.add_enabled(app.editor.can_redo(), egui::Button::new("↷ 重做")).clicked() {
+                            app.redo_edit();
+                        }
+                    });
+                });
+                if !editable {
+                    app.editor.enabled = false;
+                }
+            });
+
+            ui.separator();
+
+            ui.group(|ui| {
+                ui.label("迷宫文件 (文本 / 图片):");
+                ui.text_edit_singleline(&mut app.file_path);
+                ui.horizontal(|ui| {
+                    if ui.button("📂 打开").clicked() {
+                        app.open_text_map();
+                    }
+                    if ui.button("🖼 导入图片").clicked() {
+                        app.open_image_map();
+                    }
+                    let savable = app.original_map.topology == Topology::Square && app.original_map.floors == 1;
+                    if ui.add_enabled(savable, egui::Button::new("💾 保存")).clicked() {
+                        app.save_text_map();
+                    }
+                });
+                ui.add(egui::Slider::new(&mut app.image_import.threshold, 0..=255).text("墙亮度阈值"));
+                ui.add(egui::Slider::new(&mut app.image_import.cell_pixels, 1..=16).text("像素/格"));
+                if !app.file_status.is_empty() {
+                    ui.label(&app.file_status);
+                }
+            });
+
+            ui.separator();
+
+            ui.group(|ui| {
+                ui.label("导出:");
+                ui.text_edit_singleline(&mut app.export_path);
+                ui.add(egui::Slider::new(&mut app.export_scale, 1..=40).text("像素/格"));
+                let exportable = matches!(app.map.topology, Topology::Square | Topology::Torus);
+                ui.checkbox(&mut app.export_visited, "着色搜索状态");
+                ui.add_enabled_ui(exportable, |ui| {
+                    ui.horizontal(|ui| {
+                        if ui.button("📷 导出 PNG").clicked() {
+                            app.export_map(false);
+                        }
+                        if ui.button("📐 导出 SVG").clicked() {
+                            app.export_map(true);
+                        }
+                    });
+                });
+                ui.add(egui::Slider::new(&mut app.export_stride, 1..=200).text("步/帧"));
+                ui.add_enabled_ui(exportable && !app.steps.is_empty(), |ui| {
+                    ui.horizontal(|ui| {
+                        if ui.button("🎞 导出 GIF").clicked() {
+                            app.export_animation(true);
+                        }
+                        if ui.button("🗂 导出帧序列").clicked() {
+                            app.export_animation(false);
+                        }
+                    });
+                });
+            });
+
+            ui.separator();
+
+            ui.group(|ui| {
+                ui.label("尺寸:");
+                // 拖动结束（或键盘 / 点击修改）后重新生成地图，避免拖动过程中反复生成
+                let settled = |r: &egui::Response| r.drag_stopped() || (r.changed() && !r.dragged());
+                let width = ui.add(egui::Slider::new(&mut app.map_width, MAP_DIM_RANGE).text("宽"));
+                let height = ui.add(egui::Slider::new(&mut app.map_height, MAP_DIM_RANGE).text("高"));
+                if settled(&width) || settled(&height) {
+                    app.generate_new_map();
+                }
+                ui.add(egui::Slider::new(&mut app.render_state.cell_size, CELL_SIZE_RANGE).step_by(1.0).text("格子像素"));
+            });
+
+            ui.separator();
+
+            ui.group(|ui| {
+                ui.label("地图参数 (生成新地图时生效):");
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut app.topology, Topology::Square, "⬛ 方形");
+                    ui.radio_value(&mut app.topology, Topology::Torus, "🍩 环面");
+                    ui.radio_value(&mut app.topology, Topology::Hex, "⬢ 六边形");
+                    ui.radio_value(&mut app.topology, Topology::Polar, "🎯 圆环");
+                });
+                ui.add_enabled_ui(app.topology != Topology::Polar, |ui| ui.horizontal(|ui| {
+                    ui.label("外形:");
+                    ui.radio_value(&mut app.shape, MazeShape::Rect, "矩形");
+                    ui.radio_value(&mut app.shape, MazeShape::Circle, "圆形");
+                    ui.radio_value(&mut app.shape, MazeShape::Diamond, "菱形");
+                }));
+                ui.label("传送门对数:");
+                ui.add(egui::Slider::new(&mut app.portal_pairs, 0..=10));
+                ui.label("传送门代价:");
+                ui.add(egui::Slider::new(&mut app.portal_cost, 0..=20));
+                ui.label("单向门数量:");
+                ui.add(egui::Slider::new(&mut app.door_count, 0..=50));
+                ui.add_enabled_ui(app.topology == Topology::Square && app.shape == MazeShape::Rect, |ui| {
+                    ui.label("楼层数:");
+                    ui.add(egui::Slider::new(&mut app.floors, 1..=5));
+                });
+            });
+
+            if app.map.floors > 1 {
+                ui.separator();
+
+                ui.group(|ui| {
+                    ui.label("显示楼层:");
+                    ui.horizontal_wrapped(|ui| {
+                        for f in 0..app.map.floors {
+                            if ui.selectable_label(app.render_state.floor == f, format!("{}F", f + 1)).clicked() {
+                                app.render_state.floor = f;
+                                app.render_state.texture_dirty = true;
+                            }
+                        }
+                    });
+                });
+            }
         });
     });
 }
 
 pub fn right_panel(ctx: &egui::Context, app: &mut MazeApp) {
-    let canvas_height = (MAP_SIZE + 1) as f32 * CELL_SIZE;
+    let canvas_height = canvas_size(&app.map, app.render_state.cell_size).y;
     egui::SidePanel::right("right_panel").min_width(230.0).show(ctx, |ui| {
         ui.set_max_height(canvas_height);
         egui::ScrollArea::vertical().max_height(canvas_height).show(ui, |ui| {
@@ -91,14 +233,35 @@ pub fn right_panel(ctx: &egui::Context, app: &mut MazeApp) {
             ui.group(|ui| {
                 ui.label("📊 统计信息:");
                 ui.label(format!("算法: {}", app.algorithm.short_name()));
-                ui.label(format!("状态: {}", if app.running { "运行中" } else { "已停止" }));
+                let state = if app.search.is_some() { "搜索中" } else if app.running { "运行中" } else { "已停止" };
+                ui.label(format!("状态: {}", state));
                 ui.label(format!("自动播放: {}", if app.auto_play { "是" } else { "否" }));
                 ui.label(format!("当前步骤: {} / {}", app.step_index, app.steps.len()));
                 ui.label(format!("找到路径: {}", if app.found { "是" } else { "否" }));
+                if let Some(e) = &app.search_error {
+                    ui.colored_label(egui::Color32::from_rgb(255, 106, 106), format!("无法搜索: {}", e));
+                }
+                if let Some(status) = app.status.filter(SearchStatus::is_partial) {
+                    ui.label(format!("搜索结果: {}（部分结果）", status.name()));
+                }
                 if app.found {
                     ui.label(format!("路径长度: {}", app.path_length));
                 }
-                ui.label(format!("耗时: {} ms", app.elapsed_ms));
+                ui.label(format!("回放耗时: {} ms", app.elapsed_ms));
+            });
+
+            ui.separator();
+
+            ui.group(|ui| {
+                let stats = &app.stats;
+                ui.label("🔬 搜索统计:");
+                ui.label(format!("扩展节点: {}", stats.nodes_expanded));
+                ui.label(format!("生成节点: {}", stats.nodes_generated));
+                ui.label(format!("最大待扩展集合: {}", stats.max_frontier));
+                if app.algorithm == Algorithm::AStar {
+                    ui.label(format!("重新打开: {}", stats.reopened));
+                }
+                ui.label(format!("算法耗时: {:.3} ms", stats.elapsed.as_secs_f64() * 1000.0));
             });
 
             ui.separator();
@@ -129,6 +292,18 @@ pub fn right_panel(ctx: &egui::Context, app: &mut MazeApp) {
                     ui.colored_label(egui::Color32::from_rgb(127, 255, 212), "■");
                     ui.label("最终路径");
                 });
+                ui.horizontal(|ui| {
+                    ui.colored_label(crate::config::PORTAL_COLOR, "◎");
+                    ui.label("传送门");
+                });
+                ui.horizontal(|ui| {
+                    ui.colored_label(crate::config::DOOR_COLOR, "▶");
+                    ui.label("单向门");
+                });
+                ui.horizontal(|ui| {
+                    ui.colored_label(crate::config::STAIR_COLOR, "▲");
+                    ui.label("楼梯 / 换层位置");
+                });
             });
 
             ui.separator();
@@ -139,6 +314,7 @@ pub fn right_panel(ctx: &egui::Context, app: &mut MazeApp) {
                 ui.label("R: 重置地图");
                 ui.label("N: 生成新地图");
                 ui.label("←/→: 上一步/下一步");
+                ui.label("Ctrl+Z / Ctrl+Y: 撤销/重做编辑");
             });
         });
     });